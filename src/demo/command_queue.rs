@@ -0,0 +1,236 @@
+//! Scripted tank actions for tutorials and cutscenes.
+//!
+//! A [`CommandQueue`] drives its tank through [`TankInput`], so scripted tanks
+//! move with the same movement systems as everyone else instead of teleporting.
+
+use std::{collections::VecDeque, f32::consts::FRAC_PI_4};
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        player::{TankInput, apply_tank_input},
+        tank_movement::{MaxFrameDelta, TankMovementController, TankVisual, shortest_angle_delta},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CommandQueue>();
+
+    app.add_systems(
+        Update,
+        run_command_queue
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems)
+            .before(apply_tank_input),
+    );
+}
+
+/// How close a tank has to get to a [`TankCommand::MoveTo`] target, in world units.
+const ARRIVAL_TOLERANCE: f32 = 2.0;
+
+/// How close a tank has to get to a [`TankCommand::RotateTo`] heading, in radians.
+const HEADING_TOLERANCE: f32 = 0.01;
+
+/// A single scripted action.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum TankCommand {
+    /// Drive to a world position.
+    MoveTo(Vec2),
+    /// Turn the chassis to a world-space heading in radians.
+    RotateTo(f32),
+    /// Fire once.
+    Fire,
+    /// Do nothing for a number of seconds.
+    Wait(f32),
+}
+
+/// A list of commands that a tank executes in order.
+#[derive(Component, Debug, Clone, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+#[require(TankInput)]
+pub struct CommandQueue {
+    /// The commands left to execute, starting with the current one.
+    pub commands: VecDeque<TankCommand>,
+
+    /// Seconds spent on the current command.
+    pub elapsed: f32,
+}
+
+fn run_command_queue(
+    time: Res<Time>,
+    max_delta: Res<MaxFrameDelta>,
+    mut queue_query: Query<(
+        &mut CommandQueue,
+        &mut TankInput,
+        &TankMovementController,
+        &Transform,
+        Option<&TankVisual>,
+    )>,
+) {
+    // Plan with the same clamped delta that the movement systems simulate
+    let delta = max_delta.delta_secs(&time);
+    for (mut queue, mut input, controller, transform, visual) in &mut queue_query {
+        *input = TankInput::default();
+        let Some(&command) = queue.commands.front() else {
+            continue;
+        };
+        queue.elapsed += delta;
+
+        let local_forward = visual.map_or(Vec3::X, TankVisual::local_forward);
+        let heading = (transform.rotation * local_forward).xy().to_angle();
+        // The intent that turns the tank by `difference` this frame, without overshooting
+        let max_rotation_delta =
+            (controller.rotation_speed * delta).min(controller.max_rotation_step);
+        let rotation_toward = |difference: f32| {
+            if max_rotation_delta > 0.0 {
+                (difference / max_rotation_delta).clamp(-1.0, 1.0)
+            } else {
+                0.0
+            }
+        };
+
+        let done = match command {
+            TankCommand::MoveTo(target) => {
+                let offset = target - transform.translation.xy();
+                let distance = offset.length();
+                if distance <= ARRIVAL_TOLERANCE {
+                    true
+                } else {
+                    let difference = shortest_angle_delta(heading, offset.to_angle());
+                    input.rotation = rotation_toward(difference);
+                    // Only drive once roughly facing the target, and slow down in time to
+                    // stop on it rather than coasting past. The movement systems brake in
+                    // steps of `braking * delta` and move after each step, so from speed `v`
+                    // a tank covers about `v² / 2b + v·delta / 2` before it stops.
+                    if difference.abs() < FRAC_PI_4
+                        && controller.max_speed > 0.0
+                        && controller.braking > 0.0
+                    {
+                        let braking = controller.braking;
+                        let stopping_speed = braking
+                            * ((delta * delta / 4.0 + 2.0 * distance / braking).sqrt()
+                                - delta / 2.0);
+                        let arrival_speed = if delta > 0.0 { distance / delta } else { 0.0 };
+                        input.forward =
+                            (stopping_speed.min(arrival_speed) / controller.max_speed).min(1.0);
+                    }
+                    false
+                }
+            }
+            TankCommand::RotateTo(target) => {
+                let difference = shortest_angle_delta(heading, target);
+                input.rotation = rotation_toward(difference);
+                difference.abs() <= HEADING_TOLERANCE
+            }
+            TankCommand::Fire => {
+                input.fire = true;
+                true
+            }
+            TankCommand::Wait(duration) => queue.elapsed >= duration,
+        };
+
+        if done {
+            queue.commands.pop_front();
+            queue.elapsed = 0.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f32::consts::FRAC_PI_2, time::Duration};
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use crate::demo::tank_movement::apply_tank_movement;
+
+    use super::*;
+
+    /// An app that runs command queues through the movement systems at 20 updates per second.
+    fn queue_app(commands: impl IntoIterator<Item = TankCommand>) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            50,
+        )));
+        app.init_resource::<MaxFrameDelta>();
+        app.add_systems(
+            Update,
+            (run_command_queue, apply_tank_input, apply_tank_movement).chain(),
+        );
+        let tank = app
+            .world_mut()
+            .spawn((
+                CommandQueue {
+                    commands: commands.into_iter().collect(),
+                    ..default()
+                },
+                TankMovementController::default(),
+                Transform::default(),
+            ))
+            .id();
+        (app, tank)
+    }
+
+    fn remaining(app: &App, tank: Entity) -> usize {
+        app.world()
+            .get::<CommandQueue>(tank)
+            .unwrap()
+            .commands
+            .len()
+    }
+
+    /// Update until fewer than `count` commands remain, returning how many updates it took.
+    fn run_until_fewer_than(app: &mut App, tank: Entity, count: usize) -> usize {
+        (1..=400)
+            .find(|_| {
+                app.update();
+                remaining(app, tank) < count
+            })
+            .expect("the command never finished")
+    }
+
+    #[test]
+    fn move_to_arrives_and_moves_on() {
+        let target = Vec2::new(200.0, 100.0);
+        let (mut app, tank) = queue_app([TankCommand::MoveTo(target), TankCommand::Wait(10.0)]);
+        run_until_fewer_than(&mut app, tank, 2);
+
+        let position = app.world().get::<Transform>(tank).unwrap().translation.xy();
+        assert!(position.distance(target) <= ARRIVAL_TOLERANCE, "{position}");
+        // The tank stops on the target instead of coasting past it
+        app.update();
+        let stopped = app.world().get::<Transform>(tank).unwrap().translation.xy();
+        assert!(stopped.distance(target) <= ARRIVAL_TOLERANCE, "{stopped}");
+        assert_eq!(
+            app.world().get::<CommandQueue>(tank).unwrap().commands[0],
+            TankCommand::Wait(10.0)
+        );
+    }
+
+    #[test]
+    fn rotate_to_turns_to_the_heading() {
+        let (mut app, tank) = queue_app([TankCommand::RotateTo(FRAC_PI_2)]);
+        run_until_fewer_than(&mut app, tank, 1);
+
+        let transform = app.world().get::<Transform>(tank).unwrap();
+        let heading = (transform.rotation * Vec3::X).xy().to_angle();
+        assert!(
+            (heading - FRAC_PI_2).abs() <= HEADING_TOLERANCE,
+            "{heading}"
+        );
+        assert_eq!(transform.translation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn wait_lasts_its_duration() {
+        let (mut app, tank) = queue_app([TankCommand::Wait(0.5)]);
+        // The first update only starts the clock
+        let updates = run_until_fewer_than(&mut app, tank, 1);
+        assert_eq!(updates, 11);
+        let transform = app.world().get::<Transform>(tank).unwrap();
+        assert_eq!(*transform, Transform::default());
+    }
+}
@@ -0,0 +1,342 @@
+//! Enemy tanks that pursue the nearest player.
+//!
+//! Enemies steer through their own [`TankMovementController`] and [`TurretController`],
+//! so the regular movement pipeline drives them just like the player's tank.
+
+use std::f32::consts::PI;
+
+use bevy::{prelude::*, window::PrimaryWindow};
+use rand::Rng;
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        ZLayers,
+        player::{
+            FireController, Player, PlayerAssets, TankCustomization, TurretController,
+            aim_turret_at_cursor, apply_tank_input, apply_turret_movement, tank,
+        },
+        tank_movement::{
+            ArenaBounds, MaxFrameDelta, ScreenWrap, TankMovementController, TankVisual,
+            shortest_angle_delta, wrap_area_size, wrapped_offset,
+        },
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<EnemyController>();
+
+    // Runs after the player's input and cursor aiming, so neither steers enemies.
+    app.add_systems(
+        Update,
+        drive_enemy_tanks
+            .after(apply_tank_input)
+            .after(aim_turret_at_cursor)
+            .before(apply_turret_movement)
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems),
+    );
+}
+
+/// An enemy tank, facing up.
+pub fn enemy(
+    max_speed: f32,
+    position: Vec2,
+    visual: TankVisual,
+    customization: &TankCustomization,
+    player_assets: &PlayerAssets,
+    z_layers: &ZLayers,
+) -> impl Bundle {
+    (
+        tank(
+            "Enemy",
+            max_speed,
+            position,
+            visual,
+            customization,
+            player_assets,
+            z_layers,
+        ),
+        EnemyController::default(),
+    )
+}
+
+/// How close to the player spawn at the center of the arena an enemy may spawn, in world units.
+const MIN_SPAWN_DISTANCE: f32 = 250.0;
+
+/// How far from the arena's edges an enemy spawns at least, in world units.
+const SPAWN_EDGE_MARGIN: f32 = 50.0;
+
+/// A random enemy spawn position inside `arena`, away from the player spawn at its center.
+/// Pass the [`GameRng`](crate::demo::rng::GameRng) so that seeded runs spawn the same way.
+pub fn enemy_spawn_position(rng: &mut impl Rng, arena: &ArenaBounds) -> Vec2 {
+    let half_size = (arena.size / 2.0 - SPAWN_EDGE_MARGIN).max(Vec2::ZERO);
+    let direction = Vec2::from_angle(rng.gen_range(-PI..PI));
+    // How far the arena reaches in that direction
+    let reach = (half_size / direction.abs()).min_element();
+    direction * rng.gen_range(MIN_SPAWN_DISTANCE.min(reach)..=reach)
+}
+
+/// Drives a tank toward the nearest [`Player`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct EnemyController {
+    /// How close to the player the tank stops advancing, in world units.
+    pub engage_distance: f32,
+
+    /// How far off the player's direction the tank may face and still drive forward,
+    /// and its turret may point and still fire, in radians.
+    pub facing_threshold: f32,
+
+    /// How close the player has to be for the tank to fire, in world units.
+    pub fire_range: f32,
+}
+
+impl Default for EnemyController {
+    fn default() -> Self {
+        Self {
+            engage_distance: 250.0,
+            facing_threshold: f32::to_radians(20.0),
+            fire_range: 600.0,
+        }
+    }
+}
+
+/// System to steer enemy chassis and turrets toward the nearest player.
+fn drive_enemy_tanks(
+    time: Res<Time>,
+    max_delta: Res<MaxFrameDelta>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    arena: Res<ArenaBounds>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+    mut enemy_query: Query<
+        (
+            &EnemyController,
+            &mut TankMovementController,
+            &GlobalTransform,
+            Option<&TankVisual>,
+            Option<&Children>,
+            Has<ScreenWrap>,
+        ),
+        Without<Player>,
+    >,
+    mut turret_query: Query<(
+        &mut TurretController,
+        &GlobalTransform,
+        Option<&mut FireController>,
+    )>,
+) {
+    let dt = max_delta.delta_secs(&time);
+    // Turn at up to full speed without overshooting the target
+    let turn_intent = |difference: f32, rotation_speed: f32, max_rotation_step: f32| {
+        let max_rotation_delta = (rotation_speed * dt).min(max_rotation_step);
+        if max_rotation_delta > 0.0 {
+            (difference / max_rotation_delta).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        }
+    };
+
+    let wrap_size = wrap_area_size(window.single().ok().map(Window::size), &arena);
+
+    for (enemy, mut controller, global_transform, visual, children, wraps) in &mut enemy_query {
+        let position = global_transform.translation().xy();
+        // A wrapping tank can reach players across the screen edges
+        let to_target = player_query
+            .iter()
+            .map(|player| {
+                let offset = player.translation().xy() - position;
+                if wraps {
+                    wrapped_offset(offset, wrap_size)
+                } else {
+                    offset
+                }
+            })
+            .min_by(|a, b| a.length_squared().total_cmp(&b.length_squared()));
+
+        controller.heading_intent = None;
+        let Some(to_target) = to_target else {
+            controller.forward_intent = 0.0;
+            controller.rotation_intent = 0.0;
+            if let Some(children) = children {
+                let mut turrets = turret_query.iter_many_mut(children);
+                while let Some((mut turret, _, fire_controller)) = turrets.fetch_next() {
+                    turret.rotation_intent = 0.0;
+                    if let Some(mut fire_controller) = fire_controller {
+                        fire_controller.fire_intent = false;
+                    }
+                }
+            }
+            continue;
+        };
+        let target = position + to_target;
+        let distance = to_target.length();
+
+        // Rotate the chassis toward the player, and only advance once roughly facing them
+        let local_forward = visual.map_or(Vec3::X, TankVisual::local_forward);
+        let heading = (global_transform.rotation() * local_forward)
+            .xy()
+            .to_angle();
+        let difference = shortest_angle_delta(heading, to_target.to_angle());
+        controller.rotation_intent = turn_intent(
+            difference,
+            controller.rotation_speed,
+            controller.max_rotation_step,
+        );
+        controller.forward_intent =
+            if difference.abs() <= enemy.facing_threshold && distance > enemy.engage_distance {
+                1.0
+            } else {
+                0.0
+            };
+
+        // The turret is a child of the tank
+        let Some(children) = children else {
+            continue;
+        };
+        let mut turrets = turret_query.iter_many_mut(children);
+        while let Some((mut turret, turret_transform, fire_controller)) = turrets.fetch_next() {
+            let turret_angle = (turret_transform.rotation() * local_forward)
+                .xy()
+                .to_angle();
+            let turret_target = (target - turret_transform.translation().xy()).to_angle();
+            let difference = shortest_angle_delta(turret_angle, turret_target);
+            turret.rotation_intent =
+                turn_intent(difference, turret.rotation_speed, turret.max_rotation_step);
+            // Fire once the barrel points roughly at the player
+            if let Some(mut fire_controller) = fire_controller {
+                fire_controller.fire_intent =
+                    difference.abs() <= enemy.facing_threshold && distance <= enemy.fire_range;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f32::consts::FRAC_PI_2, time::Duration};
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use crate::demo::tank_movement::apply_tank_movement;
+
+    use super::*;
+
+    /// An app that drives enemies and their chassis at 10 updates per second.
+    fn enemy_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TransformPlugin));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        app.init_resource::<MaxFrameDelta>();
+        app.init_resource::<ArenaBounds>();
+        app.add_systems(Update, (drive_enemy_tanks, apply_tank_movement).chain());
+        // The first update only starts the clock
+        app.update();
+        app
+    }
+
+    /// Spawn an enemy facing up at `position`, with a turret, returning both entities.
+    fn spawn_enemy(app: &mut App, position: Vec2) -> (Entity, Entity) {
+        let enemy = app
+            .world_mut()
+            .spawn((
+                EnemyController::default(),
+                TankMovementController::default(),
+                Transform::from_translation(position.extend(0.0))
+                    .with_rotation(Quat::from_rotation_z(FRAC_PI_2)),
+            ))
+            .id();
+        let turret = app
+            .world_mut()
+            .spawn((
+                TurretController::default(),
+                FireController::default(),
+                Transform::default(),
+                ChildOf(enemy),
+            ))
+            .id();
+        (enemy, turret)
+    }
+
+    fn heading(app: &App, enemy: Entity) -> f32 {
+        let transform = app.world().get::<Transform>(enemy).unwrap();
+        (transform.rotation * Vec3::X).xy().to_angle()
+    }
+
+    #[test]
+    fn enemy_turns_to_face_the_player() {
+        let mut app = enemy_app();
+        let (enemy, _) = spawn_enemy(&mut app, Vec2::ZERO);
+        app.world_mut()
+            .spawn((Player, Transform::from_xyz(1000.0, 0.0, 0.0)));
+
+        let start = heading(&app, enemy).abs();
+        for _ in 0..20 {
+            app.update();
+        }
+        let heading = heading(&app, enemy);
+        assert!(heading.abs() < start);
+        assert!(heading.abs() < 0.05, "{heading}");
+        // Facing the player, it advances toward them
+        let controller = app.world().get::<TankMovementController>(enemy).unwrap();
+        assert_eq!(controller.forward_intent, 1.0);
+    }
+
+    #[test]
+    fn enemy_fires_when_aimed_and_in_range() {
+        let mut app = enemy_app();
+        let (_, turret) = spawn_enemy(&mut app, Vec2::ZERO);
+        let player = app
+            .world_mut()
+            .spawn((Player, Transform::from_xyz(0.0, 300.0, 0.0)))
+            .id();
+        let fire_intent = |app: &App| {
+            app.world()
+                .get::<FireController>(turret)
+                .unwrap()
+                .fire_intent
+        };
+
+        // The turret already points up at the player.
+        // Global transforms are only up to date from the second update
+        app.update();
+        app.update();
+        assert!(fire_intent(&app));
+
+        // Out of range it holds fire
+        app.world_mut()
+            .get_mut::<Transform>(player)
+            .unwrap()
+            .translation
+            .y = 2000.0;
+        app.update();
+        app.update();
+        assert!(!fire_intent(&app));
+    }
+
+    #[test]
+    fn wrapping_enemy_chases_across_the_edge() {
+        let mut app = enemy_app();
+        let (enemy, _) = spawn_enemy(&mut app, Vec2::new(600.0, 0.0));
+        app.world_mut()
+            .spawn((Player, Transform::from_xyz(-600.0, 0.0, 0.0)));
+        // Global transforms are only up to date from the second update
+        app.update();
+        app.update();
+        // Straight across is west, so turning counter-clockwise from up would be shorter
+        let rotation_intent = |app: &App| {
+            app.world()
+                .get::<TankMovementController>(enemy)
+                .unwrap()
+                .rotation_intent
+        };
+        assert!(rotation_intent(&app) > 0.0);
+
+        // Across the edge the player is just east, a clockwise turn away
+        app.world_mut().entity_mut(enemy).insert(ScreenWrap);
+        app.update();
+        assert!(rotation_intent(&app) < 0.0);
+    }
+}
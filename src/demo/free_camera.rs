@@ -0,0 +1,259 @@
+//! A detached free-fly camera for spectating and debugging.
+//! Press [`TOGGLE_KEY`] during gameplay to fly the camera around with WASD;
+//! tank controls are ignored until it's toggled off again, and the camera returns to where it was.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        input_command::{InputContext, InputContextStack},
+        intro_camera::IntroCamera,
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<FreeCamera>();
+
+    app.add_systems(OnExit(Screen::Gameplay), stop_free_camera);
+    app.add_systems(
+        Update,
+        (
+            toggle_free_camera.run_if(input_just_pressed(TOGGLE_KEY)),
+            move_free_camera,
+        )
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+const TOGGLE_KEY: KeyCode = KeyCode::F8;
+
+/// A camera flown directly from input instead of following the action.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct FreeCamera {
+    /// How fast the camera flies in world units per second at a projection scale of 1.0.
+    pub speed: f32,
+
+    /// Where the camera was before it started flying, to return to when it stops.
+    pub return_transform: Transform,
+
+    /// The orthographic projection scale before the camera started flying.
+    pub return_scale: f32,
+}
+
+impl Default for FreeCamera {
+    fn default() -> Self {
+        Self {
+            speed: 600.0,
+            return_transform: Transform::default(),
+            return_scale: 1.0,
+        }
+    }
+}
+
+fn toggle_free_camera(
+    mut commands: Commands,
+    mut contexts: ResMut<InputContextStack>,
+    mut camera_query: Query<
+        (Entity, Option<&FreeCamera>, &mut Transform, &mut Projection),
+        (With<Camera2d>, Without<IntroCamera>),
+    >,
+) {
+    for (camera, free_camera, mut transform, mut projection) in &mut camera_query {
+        match free_camera {
+            Some(free_camera) => {
+                return_camera(free_camera, &mut transform, &mut projection);
+                commands.entity(camera).remove::<FreeCamera>();
+                if contexts.top() == InputContext::FreeCamera {
+                    contexts.pop();
+                }
+            }
+            None => {
+                commands.entity(camera).insert(FreeCamera {
+                    return_transform: *transform,
+                    return_scale: orthographic_scale(&projection),
+                    ..default()
+                });
+                contexts.push(InputContext::FreeCamera);
+            }
+        }
+    }
+}
+
+/// Put a free camera back where it was before it started flying.
+fn return_camera(free_camera: &FreeCamera, transform: &mut Transform, projection: &mut Projection) {
+    *transform = free_camera.return_transform;
+    if let Projection::Orthographic(orthographic) = projection {
+        orthographic.scale = free_camera.return_scale;
+    }
+}
+
+fn orthographic_scale(projection: &Projection) -> f32 {
+    match projection {
+        Projection::Orthographic(orthographic) => orthographic.scale,
+        _ => 1.0,
+    }
+}
+
+fn move_free_camera(
+    time: Res<Time>,
+    input: Res<ButtonInput<KeyCode>>,
+    mut camera_query: Query<(&FreeCamera, &mut Transform, &Projection)>,
+) {
+    // Collect directional input (WASD keys)
+    let mut direction = Vec2::ZERO;
+    if input.pressed(KeyCode::KeyW) {
+        direction.y += 1.0;
+    }
+    if input.pressed(KeyCode::KeyS) {
+        direction.y -= 1.0;
+    }
+    if input.pressed(KeyCode::KeyA) {
+        direction.x -= 1.0;
+    }
+    if input.pressed(KeyCode::KeyD) {
+        direction.x += 1.0;
+    }
+    let direction = direction.normalize_or_zero();
+
+    for (free_camera, mut transform, projection) in &mut camera_query {
+        // Fly faster when zoomed out, so the speed feels the same on screen
+        let scale = orthographic_scale(projection);
+        let translation_delta = direction * free_camera.speed * scale * time.delta_secs();
+        transform.translation += translation_delta.extend(0.0);
+    }
+}
+
+/// Hand control back to the tank when leaving the level.
+fn stop_free_camera(
+    mut commands: Commands,
+    mut contexts: ResMut<InputContextStack>,
+    mut camera_query: Query<(Entity, &FreeCamera, &mut Transform, &mut Projection)>,
+) {
+    for (camera, free_camera, mut transform, mut projection) in &mut camera_query {
+        return_camera(free_camera, &mut transform, &mut projection);
+        commands.entity(camera).remove::<FreeCamera>();
+    }
+    contexts
+        .stack
+        .retain(|&context| context != InputContext::FreeCamera);
+    if contexts.stack.is_empty() {
+        contexts.stack.push(InputContext::Gameplay);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use crate::demo::{
+        input_command::{
+            GamepadDeadzone, InputCommandBuffer, KeyBindings, apply_input_commands,
+            queue_input_command,
+        },
+        tank_movement::{
+            ControlScheme, InvertControls, MaxFrameDelta, TankMovementController,
+            apply_tank_movement, record_tank_input,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn toggling_off_returns_the_camera() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_resource::<InputContextStack>();
+        app.add_systems(Update, toggle_free_camera);
+        let start = Transform::from_xyz(10.0, 20.0, 0.0);
+        let camera = app.world_mut().spawn((Camera2d, start)).id();
+
+        // Toggle on, then fly and zoom away
+        app.update();
+        assert_eq!(
+            app.world().resource::<InputContextStack>().top(),
+            InputContext::FreeCamera
+        );
+        let mut entity = app.world_mut().entity_mut(camera);
+        entity.get_mut::<Transform>().unwrap().translation = Vec3::new(500.0, -300.0, 0.0);
+        if let Projection::Orthographic(orthographic) =
+            &mut *entity.get_mut::<Projection>().unwrap()
+        {
+            orthographic.scale = 3.0;
+        }
+
+        // Toggle off
+        app.update();
+        let entity = app.world().entity(camera);
+        assert!(!entity.contains::<FreeCamera>());
+        assert_eq!(*entity.get::<Transform>().unwrap(), start);
+        assert_eq!(orthographic_scale(entity.get::<Projection>().unwrap()), 1.0);
+        assert_eq!(
+            app.world().resource::<InputContextStack>().top(),
+            InputContext::Gameplay
+        );
+    }
+
+    #[test]
+    fn wasd_flies_the_camera_instead_of_the_tank() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<KeyBindings>();
+        app.init_resource::<GamepadDeadzone>();
+        app.init_resource::<ControlScheme>();
+        app.init_resource::<InvertControls>();
+        app.init_resource::<MaxFrameDelta>();
+        app.init_resource::<InputCommandBuffer>();
+        let mut contexts = InputContextStack::default();
+        contexts.push(InputContext::FreeCamera);
+        app.insert_resource(contexts);
+        app.add_systems(
+            Update,
+            (
+                record_tank_input,
+                queue_input_command,
+                apply_input_commands,
+                apply_tank_movement,
+                move_free_camera,
+            )
+                .chain(),
+        );
+        let camera = app
+            .world_mut()
+            .spawn((Camera2d, FreeCamera::default(), Transform::default()))
+            .id();
+        let tank_start = Transform::from_xyz(50.0, 50.0, 0.0);
+        let tank = app
+            .world_mut()
+            .spawn((TankMovementController::default(), tank_start))
+            .id();
+        // The first update only starts the clock
+        app.update();
+
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.press(KeyCode::KeyW);
+        input.press(KeyCode::KeyD);
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let camera_position = app.world().get::<Transform>(camera).unwrap().translation;
+        assert!(camera_position.x > 0.0 && camera_position.y > 0.0);
+        assert_eq!(*app.world().get::<Transform>(tank).unwrap(), tank_start);
+        let controller = app.world().get::<TankMovementController>(tank).unwrap();
+        assert_eq!(controller.forward_intent, 0.0);
+        assert_eq!(controller.rotation_intent, 0.0);
+        assert_eq!(controller.current_speed, 0.0);
+    }
+}
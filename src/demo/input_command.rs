@@ -0,0 +1,728 @@
+//! Input sampling separated from simulation, as groundwork for lockstep networking.
+//!
+//! Input systems only fill in [`InputCommandBuffer::sampled`]. Once per tick the
+//! sampled command is queued, and [`apply_input_commands`] applies the oldest
+//! queued command to the tank and turret controllers. Commands can be
+//! recorded, sent and replayed identically by feeding the queue directly.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    demo::{
+        player::{FireController, TurretController},
+        snapshot::RemoteTank,
+        tank_movement::TankMovementController,
+    },
+    menus::Menu,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<InputCommand>();
+    app.register_type::<InputCommandBuffer>();
+    app.init_resource::<InputCommandBuffer>();
+    app.register_type::<InputContextStack>();
+    app.init_resource::<InputContextStack>();
+    app.register_type::<GamepadDeadzone>();
+    app.init_resource::<GamepadDeadzone>();
+    app.register_type::<KeyBindingSet>();
+    app.register_type::<PlayerKeys>();
+    app.register_type::<KeyBindings>();
+    app.init_resource::<KeyBindings>();
+
+    // Any open menu takes input away from gameplay.
+    app.add_systems(OnExit(Menu::None), push_menu_context);
+    app.add_systems(OnEnter(Menu::None), pop_menu_context);
+}
+
+/// An action that can be bound to a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum InputAction {
+    Forward,
+    Backward,
+    RotateLeft,
+    RotateRight,
+    TurretLeft,
+    TurretRight,
+    Fire,
+}
+
+impl InputAction {
+    /// Every action, in the order they're listed in menus.
+    pub const ALL: [Self; 7] = [
+        Self::Forward,
+        Self::Backward,
+        Self::RotateLeft,
+        Self::RotateRight,
+        Self::TurretLeft,
+        Self::TurretRight,
+        Self::Fire,
+    ];
+
+    /// A human-readable name for the action.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Forward => "Forward",
+            Self::Backward => "Backward",
+            Self::RotateLeft => "Rotate Left",
+            Self::RotateRight => "Rotate Right",
+            Self::TurretLeft => "Turret Left",
+            Self::TurretRight => "Turret Right",
+            Self::Fire => "Fire",
+        }
+    }
+}
+
+/// Whose keys a set of [`KeyBindings`] is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+pub enum KeyBindingSet {
+    /// The only player in a single-player game.
+    #[default]
+    SinglePlayer,
+    /// Player one in a two-player game.
+    PlayerOne,
+    /// Player two in a two-player game.
+    PlayerTwo,
+}
+
+impl KeyBindingSet {
+    /// Every binding set, in the order they're listed in menus.
+    pub const ALL: [Self; 3] = [Self::SinglePlayer, Self::PlayerOne, Self::PlayerTwo];
+
+    /// A human-readable name for the binding set.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::SinglePlayer => "Single Player",
+            Self::PlayerOne => "Player 1",
+            Self::PlayerTwo => "Player 2",
+        }
+    }
+
+    /// The binding sets that are played at the same time as this one, on the same keyboard.
+    fn sharing_keyboard(self) -> &'static [Self] {
+        match self {
+            Self::SinglePlayer => &[Self::SinglePlayer],
+            Self::PlayerOne | Self::PlayerTwo => &[Self::PlayerOne, Self::PlayerTwo],
+        }
+    }
+}
+
+/// The keys that drive one player's tank.
+/// In the arcade control scheme, the movement keys point up, down, left and right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct PlayerKeys {
+    /// The key to drive forward.
+    pub forward: KeyCode,
+
+    /// The key to drive backward.
+    pub backward: KeyCode,
+
+    /// The key to rotate the chassis counter-clockwise.
+    pub rotate_left: KeyCode,
+
+    /// The key to rotate the chassis clockwise.
+    pub rotate_right: KeyCode,
+
+    /// The key to rotate the turret counter-clockwise.
+    pub turret_left: KeyCode,
+
+    /// The key to rotate the turret clockwise.
+    pub turret_right: KeyCode,
+
+    /// The key to fire.
+    pub fire: KeyCode,
+}
+
+impl PlayerKeys {
+    /// The key bound to `action`.
+    pub fn get(&self, action: InputAction) -> KeyCode {
+        match action {
+            InputAction::Forward => self.forward,
+            InputAction::Backward => self.backward,
+            InputAction::RotateLeft => self.rotate_left,
+            InputAction::RotateRight => self.rotate_right,
+            InputAction::TurretLeft => self.turret_left,
+            InputAction::TurretRight => self.turret_right,
+            InputAction::Fire => self.fire,
+        }
+    }
+
+    fn binding_mut(&mut self, action: InputAction) -> &mut KeyCode {
+        match action {
+            InputAction::Forward => &mut self.forward,
+            InputAction::Backward => &mut self.backward,
+            InputAction::RotateLeft => &mut self.rotate_left,
+            InputAction::RotateRight => &mut self.rotate_right,
+            InputAction::TurretLeft => &mut self.turret_left,
+            InputAction::TurretRight => &mut self.turret_right,
+            InputAction::Fire => &mut self.fire,
+        }
+    }
+}
+
+/// The keys of every player. Changes take effect on the next frame.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct KeyBindings {
+    /// The keys in single-player games.
+    pub single_player: PlayerKeys,
+
+    /// Player one's keys in two-player games.
+    pub player_one: PlayerKeys,
+
+    /// Player two's keys in two-player games.
+    pub player_two: PlayerKeys,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            // WASD to drive, the arrow keys to turn the turret and Space to fire
+            single_player: PlayerKeys {
+                forward: KeyCode::KeyW,
+                backward: KeyCode::KeyS,
+                rotate_left: KeyCode::KeyA,
+                rotate_right: KeyCode::KeyD,
+                turret_left: KeyCode::ArrowLeft,
+                turret_right: KeyCode::ArrowRight,
+                fire: KeyCode::Space,
+            },
+            // Two players share the keyboard, so player one keeps to its left-hand side
+            player_one: PlayerKeys {
+                forward: KeyCode::KeyW,
+                backward: KeyCode::KeyS,
+                rotate_left: KeyCode::KeyA,
+                rotate_right: KeyCode::KeyD,
+                turret_left: KeyCode::KeyQ,
+                turret_right: KeyCode::KeyE,
+                fire: KeyCode::Space,
+            },
+            // And player two to its right-hand side
+            player_two: PlayerKeys {
+                forward: KeyCode::ArrowUp,
+                backward: KeyCode::ArrowDown,
+                rotate_left: KeyCode::ArrowLeft,
+                rotate_right: KeyCode::ArrowRight,
+                turret_left: KeyCode::Comma,
+                turret_right: KeyCode::Period,
+                fire: KeyCode::Slash,
+            },
+        }
+    }
+}
+
+impl KeyBindings {
+    /// The keys of a binding set.
+    pub fn keys(&self, set: KeyBindingSet) -> &PlayerKeys {
+        match set {
+            KeyBindingSet::SinglePlayer => &self.single_player,
+            KeyBindingSet::PlayerOne => &self.player_one,
+            KeyBindingSet::PlayerTwo => &self.player_two,
+        }
+    }
+
+    fn keys_mut(&mut self, set: KeyBindingSet) -> &mut PlayerKeys {
+        match set {
+            KeyBindingSet::SinglePlayer => &mut self.single_player,
+            KeyBindingSet::PlayerOne => &mut self.player_one,
+            KeyBindingSet::PlayerTwo => &mut self.player_two,
+        }
+    }
+
+    /// Bind `action` to `key` in a binding set. An action already bound to `key`,
+    /// by this player or by one playing at the same time, takes over `action`'s
+    /// old key, so no two actions share a key.
+    pub fn set(&mut self, set: KeyBindingSet, action: InputAction, key: KeyCode) {
+        let old_key = self.keys(set).get(action);
+        let holder = set
+            .sharing_keyboard()
+            .iter()
+            .flat_map(|&other_set| InputAction::ALL.map(|other| (other_set, other)))
+            .find(|&(other_set, other)| {
+                (other_set, other) != (set, action) && self.keys(other_set).get(other) == key
+            });
+        if let Some((other_set, other)) = holder {
+            *self.keys_mut(other_set).binding_mut(other) = old_key;
+        }
+        *self.keys_mut(set).binding_mut(action) = key;
+    }
+}
+
+/// Keys taken by developer tools, which can't be bound to actions:
+/// the free camera, telemetry flushing, frame dumps and the debug UI toggle.
+pub const RESERVED_KEYS: [KeyCode; 4] =
+    [KeyCode::F8, KeyCode::F9, KeyCode::F10, KeyCode::Backquote];
+
+/// A human-readable name for `key`, e.g. "W", "Left Shift" or "/".
+pub fn key_label(key: KeyCode) -> String {
+    let label = match key {
+        KeyCode::ArrowUp => "Up",
+        KeyCode::ArrowDown => "Down",
+        KeyCode::ArrowLeft => "Left",
+        KeyCode::ArrowRight => "Right",
+        KeyCode::ShiftLeft => "Left Shift",
+        KeyCode::ShiftRight => "Right Shift",
+        KeyCode::ControlLeft => "Left Ctrl",
+        KeyCode::ControlRight => "Right Ctrl",
+        KeyCode::AltLeft => "Left Alt",
+        KeyCode::AltRight => "Right Alt",
+        KeyCode::Backquote => "`",
+        KeyCode::Minus => "-",
+        KeyCode::Equal => "=",
+        KeyCode::BracketLeft => "[",
+        KeyCode::BracketRight => "]",
+        KeyCode::Backslash => "\\",
+        KeyCode::Semicolon => ";",
+        KeyCode::Quote => "'",
+        KeyCode::Comma => ",",
+        KeyCode::Period => ".",
+        KeyCode::Slash => "/",
+        _ => {
+            // Most other names read fine once the prefix is dropped, e.g. `KeyW` and `Digit1`
+            let name = format!("{key:?}");
+            return match name
+                .strip_prefix("Key")
+                .or_else(|| name.strip_prefix("Digit"))
+            {
+                Some(short) => short.to_string(),
+                None => name,
+            };
+        }
+    };
+    label.to_string()
+}
+
+/// How far a gamepad stick has to move before it counts as input,
+/// so stick drift near the center reads as zero.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct GamepadDeadzone {
+    /// The deadzone per stick axis, from 0.0 to 1.0.
+    pub deadzone: f32,
+}
+
+impl Default for GamepadDeadzone {
+    fn default() -> Self {
+        Self { deadzone: 0.15 }
+    }
+}
+
+impl GamepadDeadzone {
+    /// `value` with the deadzone removed, rescaled so it still spans -1.0 to 1.0.
+    pub fn apply(&self, value: f32) -> f32 {
+        let magnitude = value.abs();
+        if magnitude <= self.deadzone || self.deadzone >= 1.0 {
+            return 0.0;
+        }
+        value.signum() * ((magnitude - self.deadzone) / (1.0 - self.deadzone)).min(1.0)
+    }
+
+    /// The strongest position of a stick across all connected gamepads, with the deadzone applied.
+    pub fn stick(&self, gamepads: &Query<&Gamepad>, stick: impl Fn(&Gamepad) -> Vec2) -> Vec2 {
+        gamepads
+            .iter()
+            .map(|gamepad| self.gamepad_stick(gamepad, &stick))
+            .fold(Vec2::ZERO, |strongest, position| {
+                Vec2::new(
+                    strongest_intent(strongest.x, position.x),
+                    strongest_intent(strongest.y, position.y),
+                )
+            })
+    }
+
+    /// The position of a stick on one gamepad, with the deadzone applied.
+    pub fn gamepad_stick(&self, gamepad: &Gamepad, stick: impl Fn(&Gamepad) -> Vec2) -> Vec2 {
+        let position = stick(gamepad);
+        Vec2::new(self.apply(position.x), self.apply(position.y))
+    }
+}
+
+/// The larger-magnitude of two intents, so e.g. keyboard and gamepad input don't cancel out.
+pub fn strongest_intent(a: f32, b: f32) -> f32 {
+    if b.abs() > a.abs() { b } else { a }
+}
+
+/// Who input is currently meant for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum InputContext {
+    /// Input drives the player's tank.
+    Gameplay,
+    /// A menu is open.
+    Menu,
+    /// A dialog is open on top of gameplay or a menu.
+    Dialog,
+    /// The free camera is being flown around.
+    FreeCamera,
+}
+
+/// A stack of input contexts. Only the top context receives input,
+/// so e.g. movement keys don't drive the tank while a menu is open.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct InputContextStack {
+    /// The contexts from bottom to top. The bottom [`InputContext::Gameplay`] is never popped.
+    pub stack: Vec<InputContext>,
+}
+
+impl Default for InputContextStack {
+    fn default() -> Self {
+        Self {
+            stack: vec![InputContext::Gameplay],
+        }
+    }
+}
+
+impl InputContextStack {
+    /// The context currently receiving input.
+    pub fn top(&self) -> InputContext {
+        self.stack.last().copied().unwrap_or(InputContext::Gameplay)
+    }
+
+    /// Whether gameplay is currently receiving input.
+    pub fn is_gameplay(&self) -> bool {
+        self.top() == InputContext::Gameplay
+    }
+
+    /// Make `context` receive input until it is popped.
+    pub fn push(&mut self, context: InputContext) {
+        self.stack.push(context);
+    }
+
+    /// Remove the top context, unless it's the bottom one.
+    pub fn pop(&mut self) -> Option<InputContext> {
+        if self.stack.len() > 1 {
+            self.stack.pop()
+        } else {
+            None
+        }
+    }
+}
+
+fn push_menu_context(mut contexts: ResMut<InputContextStack>) {
+    contexts.push(InputContext::Menu);
+}
+
+fn pop_menu_context(mut contexts: ResMut<InputContextStack>) {
+    if contexts.top() == InputContext::Menu {
+        contexts.pop();
+    }
+}
+
+/// A system set for queueing live input.
+/// Add run conditions to it to hold back live input, e.g. during a cutscene.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QueueLiveInput;
+
+/// The input for a single tick.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+pub struct InputCommand {
+    /// See [`TankMovementController::forward_intent`].
+    pub forward: f32,
+
+    /// See [`TankMovementController::rotation_intent`].
+    pub rotation: f32,
+
+    /// See [`TankMovementController::heading_intent`].
+    pub heading: Option<f32>,
+
+    /// See [`TurretController::rotation_intent`].
+    pub turret_rotation: f32,
+
+    /// See [`FireController::fire_intent`].
+    pub fire: bool,
+}
+
+/// Input commands waiting to be applied.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct InputCommandBuffer {
+    /// The command being sampled from live input this tick.
+    pub sampled: InputCommand,
+
+    /// Commands waiting to be applied, oldest first.
+    pub commands: VecDeque<InputCommand>,
+
+    /// Whether live input is queued each tick.
+    /// Turn this off to replay recorded commands without live input mixing in.
+    pub queue_live_input: bool,
+}
+
+impl Default for InputCommandBuffer {
+    fn default() -> Self {
+        Self {
+            sampled: InputCommand::default(),
+            commands: VecDeque::new(),
+            queue_live_input: true,
+        }
+    }
+}
+
+/// System to queue the command sampled from live input this tick.
+pub fn queue_input_command(mut buffer: ResMut<InputCommandBuffer>) {
+    if buffer.queue_live_input {
+        let command = buffer.sampled;
+        buffer.commands.push_back(command);
+    }
+}
+
+/// System to apply the oldest queued command to all tank and turret controllers.
+/// If no command is queued, the controllers get no input.
+pub fn apply_input_commands(
+    mut buffer: ResMut<InputCommandBuffer>,
+    mut controller_query: Query<&mut TankMovementController, Without<RemoteTank>>,
+    mut turret_query: Query<(
+        &mut TurretController,
+        Option<&mut FireController>,
+        Option<&ChildOf>,
+    )>,
+    remote_query: Query<(), With<RemoteTank>>,
+) {
+    let command = buffer.commands.pop_front().unwrap_or_default();
+
+    // Remote tanks follow their snapshots, not local input
+    for mut controller in &mut controller_query {
+        controller.forward_intent = command.forward;
+        controller.rotation_intent = command.rotation;
+        controller.heading_intent = command.heading;
+    }
+    for (mut controller, fire_controller, parent) in &mut turret_query {
+        if parent.is_some_and(|parent| remote_query.contains(parent.parent())) {
+            continue;
+        }
+        controller.rotation_intent = command.turret_rotation;
+        if let Some(mut fire_controller) = fire_controller {
+            fire_controller.fire_intent = command.fire;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::demo::{
+        player::{FineAimModifier, TurretSensitivity, record_turret_input},
+        tank_movement::{ControlScheme, InvertControls, record_tank_input},
+    };
+
+    use super::*;
+
+    const EPSILON: f32 = 1e-5;
+
+    #[test]
+    fn deadzone_zeroes_small_values() {
+        let deadzone = GamepadDeadzone { deadzone: 0.2 };
+        assert_eq!(deadzone.apply(0.0), 0.0);
+        assert_eq!(deadzone.apply(0.1), 0.0);
+        assert_eq!(deadzone.apply(-0.2), 0.0);
+    }
+
+    #[test]
+    fn deadzone_rescales_to_the_full_range() {
+        let deadzone = GamepadDeadzone { deadzone: 0.2 };
+        assert!((deadzone.apply(0.6) - 0.5).abs() < EPSILON);
+        assert!((deadzone.apply(-0.6) + 0.5).abs() < EPSILON);
+        assert_eq!(deadzone.apply(1.0), 1.0);
+        assert_eq!(deadzone.apply(-1.0), -1.0);
+        // Out-of-range hardware readings don't exceed 1.0
+        assert_eq!(deadzone.apply(1.5), 1.0);
+    }
+
+    #[test]
+    fn full_deadzone_ignores_the_stick() {
+        let deadzone = GamepadDeadzone { deadzone: 1.0 };
+        assert_eq!(deadzone.apply(1.0), 0.0);
+    }
+
+    #[test]
+    fn binding_a_used_key_swaps_the_bindings() {
+        let mut bindings = KeyBindings::default();
+        bindings.set(
+            KeyBindingSet::SinglePlayer,
+            InputAction::Forward,
+            KeyCode::Space,
+        );
+        assert_eq!(bindings.single_player.forward, KeyCode::Space);
+        assert_eq!(bindings.single_player.fire, KeyCode::KeyW);
+
+        // Rebinding to the same key changes nothing
+        bindings.set(
+            KeyBindingSet::SinglePlayer,
+            InputAction::Forward,
+            KeyCode::Space,
+        );
+        assert_eq!(bindings.single_player.forward, KeyCode::Space);
+        assert_eq!(bindings.single_player.fire, KeyCode::KeyW);
+
+        // The other layouts keep their keys
+        assert_eq!(bindings.player_one, KeyBindings::default().player_one);
+    }
+
+    #[test]
+    fn players_sharing_the_keyboard_swap_keys_with_each_other() {
+        let mut bindings = KeyBindings::default();
+        bindings.set(KeyBindingSet::PlayerOne, InputAction::Fire, KeyCode::Slash);
+        assert_eq!(bindings.player_one.fire, KeyCode::Slash);
+        assert_eq!(bindings.player_two.fire, KeyCode::Space);
+        // Single-player games are played alone
+        bindings.set(
+            KeyBindingSet::PlayerOne,
+            InputAction::TurretLeft,
+            KeyCode::ArrowLeft,
+        );
+        assert_eq!(bindings.player_two.rotate_left, KeyCode::KeyQ);
+        assert_eq!(bindings.single_player.turret_left, KeyCode::ArrowLeft);
+    }
+
+    #[test]
+    fn default_layouts_never_share_a_key() {
+        let bindings = KeyBindings::default();
+        for set in KeyBindingSet::ALL {
+            let mut keys: Vec<KeyCode> = set
+                .sharing_keyboard()
+                .iter()
+                .flat_map(|&set| InputAction::ALL.map(|action| bindings.keys(set).get(action)))
+                .collect();
+            let count = keys.len();
+            keys.sort();
+            keys.dedup();
+            assert_eq!(keys.len(), count, "{set:?}");
+        }
+    }
+
+    #[test]
+    fn key_labels_are_readable() {
+        assert_eq!(key_label(KeyCode::KeyW), "W");
+        assert_eq!(key_label(KeyCode::Digit1), "1");
+        assert_eq!(key_label(KeyCode::ArrowLeft), "Left");
+        assert_eq!(key_label(KeyCode::ShiftLeft), "Left Shift");
+        assert_eq!(key_label(KeyCode::Slash), "/");
+        assert_eq!(key_label(KeyCode::Space), "Space");
+        assert_eq!(key_label(KeyCode::F5), "F5");
+    }
+
+    /// An app that records live input into commands and applies them to one tank and turret.
+    fn input_app() -> (App, Entity, Entity) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<KeyBindings>();
+        app.init_resource::<GamepadDeadzone>();
+        app.init_resource::<ControlScheme>();
+        app.init_resource::<InvertControls>();
+        app.init_resource::<FineAimModifier>();
+        app.init_resource::<TurretSensitivity>();
+        app.init_resource::<InputContextStack>();
+        app.init_resource::<InputCommandBuffer>();
+        app.add_systems(
+            Update,
+            (
+                record_tank_input,
+                record_turret_input,
+                queue_input_command,
+                apply_input_commands,
+            )
+                .chain(),
+        );
+        let tank = app
+            .world_mut()
+            .spawn(TankMovementController::default())
+            .id();
+        let turret = app
+            .world_mut()
+            .spawn((
+                TurretController::default(),
+                FireController::default(),
+                ChildOf(tank),
+            ))
+            .id();
+        (app, tank, turret)
+    }
+
+    /// The tank and turret intents: forward, rotation, turret rotation and fire.
+    fn intents(app: &App, tank: Entity, turret: Entity) -> (f32, f32, f32, bool) {
+        let world = app.world();
+        let controller = world.get::<TankMovementController>(tank).unwrap();
+        (
+            controller.forward_intent,
+            controller.rotation_intent,
+            world
+                .get::<TurretController>(turret)
+                .unwrap()
+                .rotation_intent,
+            world.get::<FireController>(turret).unwrap().fire_intent,
+        )
+    }
+
+    #[test]
+    fn menu_context_keeps_held_keys_from_the_tank() {
+        let (mut app, tank, turret) = input_app();
+        let intents = |app: &App| intents(app, tank, turret);
+
+        app.world_mut()
+            .resource_mut::<InputContextStack>()
+            .push(InputContext::Menu);
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        for key in [
+            KeyCode::KeyW,
+            KeyCode::KeyA,
+            KeyCode::ArrowLeft,
+            KeyCode::Space,
+        ] {
+            input.press(key);
+        }
+        app.update();
+        assert_eq!(intents(&app), (0.0, 0.0, 0.0, false));
+
+        // Back in gameplay, the held keys drive the tank again
+        app.world_mut().resource_mut::<InputContextStack>().pop();
+        app.update();
+        assert_eq!(intents(&app), (1.0, 1.0, 1.0, true));
+    }
+
+    #[test]
+    fn replayed_commands_match_live_input() {
+        let (mut live, live_tank, live_turret) = input_app();
+        let mut input = live.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        for key in [
+            KeyCode::KeyW,
+            KeyCode::KeyD,
+            KeyCode::ArrowLeft,
+            KeyCode::Space,
+        ] {
+            input.press(key);
+        }
+        live.update();
+        let recorded = live.world().resource::<InputCommandBuffer>().sampled;
+
+        // Replay the recorded command without any keys held
+        let (mut replay, replay_tank, replay_turret) = input_app();
+        let mut buffer = replay.world_mut().resource_mut::<InputCommandBuffer>();
+        buffer.queue_live_input = false;
+        buffer.commands.push_back(recorded);
+        replay.update();
+
+        assert_eq!(
+            intents(&replay, replay_tank, replay_turret),
+            intents(&live, live_tank, live_turret)
+        );
+        assert_eq!(
+            intents(&live, live_tank, live_turret),
+            (1.0, -1.0, 1.0, true)
+        );
+        let heading = |app: &App, tank| {
+            app.world()
+                .get::<TankMovementController>(tank)
+                .unwrap()
+                .heading_intent
+        };
+        assert_eq!(heading(&replay, replay_tank), heading(&live, live_tank));
+    }
+
+    #[test]
+    fn strongest_intent_keeps_the_larger_magnitude() {
+        assert_eq!(strongest_intent(0.5, -0.8), -0.8);
+        assert_eq!(strongest_intent(-1.0, 0.3), -1.0);
+        assert_eq!(strongest_intent(0.0, 0.0), 0.0);
+    }
+}
@@ -0,0 +1,216 @@
+//! A camera introduction at the start of a level: the camera eases from an
+//! overview of the arena down to the player spawn before handing control over.
+//! Pressing any key skips it.
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        input_command::QueueLiveInput, level::spawn_level, player::Player,
+        tank_movement::ArenaBounds,
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<IntroCamera>();
+
+    // Start once the players have spawned, so the intro knows where to end
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        start_intro_camera.after(spawn_level),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), finish_intro_camera);
+    app.add_systems(
+        Update,
+        play_intro_camera
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+
+    // Hold back live input until the intro is over.
+    app.configure_sets(
+        Update,
+        QueueLiveInput.run_if(not(any_with_component::<IntroCamera>)),
+    );
+}
+
+/// How long the intro takes in seconds.
+const INTRO_DURATION_SECS: f32 = 1.5;
+
+/// Where the camera looks and how far it's zoomed out.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct CameraPose {
+    /// The world position at the center of the view.
+    pub position: Vec2,
+
+    /// The orthographic projection scale. Larger values show more of the world.
+    pub scale: f32,
+}
+
+/// A one-shot camera move, removed once it finishes.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct IntroCamera {
+    /// The pose at the start of the intro.
+    pub from: CameraPose,
+
+    /// The pose at the end of the intro.
+    pub to: CameraPose,
+
+    /// How long the intro takes in seconds.
+    pub duration: f32,
+
+    /// Seconds since the intro started.
+    pub elapsed: f32,
+}
+
+impl IntroCamera {
+    fn pose(&self) -> CameraPose {
+        let t = if self.duration > 0.0 {
+            self.elapsed / self.duration
+        } else {
+            1.0
+        };
+        let t = EaseFunction::CubicInOut.sample_clamped(t);
+        CameraPose {
+            position: self.from.position.lerp(self.to.position, t),
+            scale: self.from.scale.lerp(self.to.scale, t),
+        }
+    }
+}
+
+fn start_intro_camera(
+    mut commands: Commands,
+    arena: Res<ArenaBounds>,
+    window_query: Query<&Window, With<bevy::window::PrimaryWindow>>,
+    camera_query: Query<Entity, With<Camera2d>>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    // Zoom out far enough to see the whole arena with some margin around it
+    let overview_scale = window_query
+        .single()
+        .map_or(1.0, |window| (arena.size / window.size()).max_element())
+        * 1.5;
+    // End centered on the player spawn, or between the spawns with two players
+    let player_count = player_query.iter().len();
+    let player_spawn = if player_count > 0 {
+        player_query
+            .iter()
+            .map(|transform| transform.translation.xy())
+            .sum::<Vec2>()
+            / player_count as f32
+    } else {
+        Vec2::ZERO
+    };
+    for camera in &camera_query {
+        commands.entity(camera).insert(IntroCamera {
+            from: CameraPose {
+                position: Vec2::ZERO,
+                scale: overview_scale,
+            },
+            to: CameraPose {
+                position: player_spawn,
+                scale: 1.0,
+            },
+            duration: INTRO_DURATION_SECS,
+            elapsed: 0.0,
+        });
+    }
+}
+
+fn play_intro_camera(
+    mut commands: Commands,
+    time: Res<Time>,
+    input: Res<ButtonInput<KeyCode>>,
+    mut camera_query: Query<(Entity, &mut IntroCamera, &mut Transform, &mut Projection)>,
+) {
+    let skip = input.get_just_pressed().next().is_some();
+    for (entity, mut intro, mut transform, mut projection) in &mut camera_query {
+        intro.elapsed += time.delta_secs();
+        if skip {
+            intro.elapsed = intro.duration;
+        }
+
+        apply_pose(intro.pose(), &mut transform, &mut projection);
+        if intro.elapsed >= intro.duration {
+            commands.entity(entity).remove::<IntroCamera>();
+        }
+    }
+}
+
+/// Jump to the end of an intro that is still playing when leaving the level.
+fn finish_intro_camera(
+    mut commands: Commands,
+    mut camera_query: Query<(Entity, &IntroCamera, &mut Transform, &mut Projection)>,
+) {
+    for (entity, intro, mut transform, mut projection) in &mut camera_query {
+        apply_pose(intro.to, &mut transform, &mut projection);
+        commands.entity(entity).remove::<IntroCamera>();
+    }
+}
+
+fn apply_pose(pose: CameraPose, transform: &mut Transform, projection: &mut Projection) {
+    transform.translation = pose.position.extend(transform.translation.z);
+    if let Projection::Orthographic(orthographic) = projection {
+        orthographic.scale = pose.scale;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{state::app::StatesPlugin, time::TimeUpdateStrategy};
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct LiveInputFrames(u32);
+
+    fn count_live_input(mut frames: ResMut<LiveInputFrames>) {
+        frames.0 += 1;
+    }
+
+    #[test]
+    fn intro_ends_on_the_player_and_hands_over_input() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<ArenaBounds>();
+        app.init_resource::<LiveInputFrames>();
+        app.init_state::<Screen>();
+        app.add_plugins(plugin);
+        app.add_systems(Update, count_live_input.in_set(QueueLiveInput));
+
+        let spawn = Vec2::new(300.0, -120.0);
+        app.world_mut()
+            .spawn((Player, Transform::from_translation(spawn.extend(0.0))));
+        let camera = app.world_mut().spawn(Camera2d).id();
+        app.world_mut()
+            .resource_mut::<NextState<Screen>>()
+            .set(Screen::Gameplay);
+
+        // The first update enters the level and only starts the clock
+        app.update();
+        let intro = app.world().get::<IntroCamera>(camera).unwrap();
+        assert_eq!(intro.to.position, spawn);
+
+        // Live input is held back for the whole intro
+        let updates = (INTRO_DURATION_SECS / 0.1).round() as u32;
+        for _ in 0..updates {
+            app.update();
+        }
+        assert_eq!(app.world().resource::<LiveInputFrames>().0, 0);
+        assert!(app.world().get::<IntroCamera>(camera).is_none());
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert_eq!(transform.translation.xy(), spawn);
+
+        app.update();
+        assert_eq!(app.world().resource::<LiveInputFrames>().0, 1);
+    }
+}
@@ -5,7 +5,13 @@ use bevy::prelude::*;
 use crate::{
     asset_tracking::LoadResource,
     audio::music,
-    demo::player::{PlayerAssets, player},
+    demo::{
+        DemoConfig, ZLayers,
+        enemy::{enemy, enemy_spawn_position},
+        player::{PlayerAssets, PlayerControls, TankCustomization, player, player_tank_visual},
+        rng::GameRng,
+        tank_movement::ArenaBounds,
+    },
     screens::Screen,
 };
 
@@ -30,23 +36,148 @@ impl FromWorld for LevelAssets {
     }
 }
 
+/// The tint of player two's tank in two-player games.
+const PLAYER_TWO_TINT: Color = Color::srgb(1.0, 0.6, 0.6);
+
+/// The tint of enemy tanks in single-player games.
+const ENEMY_TINT: Color = Color::srgb(0.6, 0.8, 0.6);
+
 /// A system that spawns the main level.
 pub fn spawn_level(
     mut commands: Commands,
+    config: Res<DemoConfig>,
     level_assets: Res<LevelAssets>,
     player_assets: Res<PlayerAssets>,
+    customization: Res<TankCustomization>,
+    z_layers: Res<ZLayers>,
+    arena: Res<ArenaBounds>,
+    mut rng: ResMut<GameRng>,
 ) {
-    commands.spawn((
-        Name::new("Level"),
-        Transform::default(),
-        Visibility::default(),
-        StateScoped(Screen::Gameplay),
-        children![
-            player(400.0, &player_assets,),
-            (
-                Name::new("Gameplay Music"),
-                music(level_assets.music.clone())
-            )
-        ],
-    ));
+    let level = commands
+        .spawn((
+            Name::new("Level"),
+            Transform::default(),
+            Visibility::default(),
+            StateScoped(Screen::Gameplay),
+        ))
+        .id();
+
+    if config.two_players {
+        // Player two gets a tint so the tanks can be told apart
+        let player_two_customization = TankCustomization {
+            primary_color: PLAYER_TWO_TINT,
+            secondary_color: PLAYER_TWO_TINT,
+            decal: None,
+        };
+        commands.spawn((
+            player(
+                400.0,
+                Vec2::new(-200.0, 0.0),
+                player_tank_visual(&player_assets),
+                &customization,
+                &player_assets,
+                &z_layers,
+            ),
+            PlayerControls::PLAYER_ONE,
+            ChildOf(level),
+        ));
+        commands.spawn((
+            player(
+                400.0,
+                Vec2::new(200.0, 0.0),
+                player_tank_visual(&player_assets),
+                &player_two_customization,
+                &player_assets,
+                &z_layers,
+            ),
+            PlayerControls::PLAYER_TWO,
+            ChildOf(level),
+        ));
+    } else {
+        commands.spawn((
+            player(
+                400.0,
+                Vec2::ZERO,
+                player_tank_visual(&player_assets),
+                &customization,
+                &player_assets,
+                &z_layers,
+            ),
+            ChildOf(level),
+        ));
+
+        let enemy_customization = TankCustomization {
+            primary_color: ENEMY_TINT,
+            secondary_color: ENEMY_TINT,
+            decal: None,
+        };
+        commands.spawn((
+            enemy(
+                300.0,
+                enemy_spawn_position(&mut *rng, &arena),
+                player_tank_visual(&player_assets),
+                &enemy_customization,
+                &player_assets,
+                &z_layers,
+            ),
+            ChildOf(level),
+        ));
+    }
+
+    if config.audio {
+        commands.spawn((
+            Name::new("Gameplay Music"),
+            music(level_assets.music.clone()),
+            ChildOf(level),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::demo::enemy::EnemyController;
+
+    /// Where the enemy spawns in a level started with `seed`.
+    fn enemy_spawn_with_seed(seed: u64) -> Vec2 {
+        let mut app = App::new();
+        app.insert_resource(DemoConfig {
+            screen_wrap: true,
+            audio: false,
+            two_players: false,
+        });
+        app.insert_resource(LevelAssets {
+            music: Handle::default(),
+        });
+        app.insert_resource(PlayerAssets {
+            tank: Handle::default(),
+            turret: Handle::default(),
+            projectile: Handle::default(),
+            steps: Vec::new(),
+            engine: Handle::default(),
+        });
+        app.init_resource::<TankCustomization>();
+        app.init_resource::<ZLayers>();
+        app.init_resource::<ArenaBounds>();
+        app.insert_resource(GameRng::new(seed));
+        app.world_mut().run_system_once(spawn_level).unwrap();
+
+        let world = app.world_mut();
+        let mut enemies = world.query_filtered::<&Transform, With<EnemyController>>();
+        enemies.single(world).unwrap().translation.xy()
+    }
+
+    #[test]
+    fn seeded_levels_spawn_enemies_in_the_same_place() {
+        let spawn = enemy_spawn_with_seed(7);
+        assert_eq!(enemy_spawn_with_seed(7), spawn);
+        assert_ne!(enemy_spawn_with_seed(8), spawn);
+
+        // Away from the player at the center, and inside the arena
+        assert!(spawn.length() >= 250.0, "{spawn}");
+        let half_size = ArenaBounds::default().size / 2.0;
+        assert!(spawn.abs().cmple(half_size).all(), "{spawn}");
+    }
 }
@@ -2,19 +2,325 @@
 //! purposes and should be replaced with your own game logic.
 //! Feel free to change the logic found here if you feel like tinkering around
 //! to get a feeling for the template.
+//!
+//! Everything is wired up by [`TanksDemoPlugin`]. Besides `DefaultPlugins`
+//! (or at least the asset, input, state and audio plugins), an embedding app
+//! needs the [`AppSystems`](crate::AppSystems) and
+//! [`PausableSystems`](crate::PausableSystems) sets configured with
+//! [`configure_system_sets`](crate::configure_system_sets):
+//!
+//! ```ignore
+//! app.add_plugins(TanksDemoPlugin::default().with_audio(false));
+//! tanks_war::configure_system_sets(&mut app);
+//! ```
+//!
+//! Once the assets have loaded, spawn the level with [`level::spawn_level`]
+//! (the `screens` module does this when entering the gameplay screen).
 
 use bevy::prelude::*;
 
+use crate::asset_tracking::{self, ResourceHandles};
+
+pub mod command_queue;
+pub mod enemy;
+pub mod free_camera;
+pub mod input_command;
+pub mod intro_camera;
 pub mod level;
 mod movement;
 pub mod player;
-mod tank_movement;
-
-pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((
-        level::plugin,
-        movement::plugin,
-        tank_movement::plugin,
-        player::plugin,
-    ));
+pub mod projectile;
+pub mod rng;
+pub mod snapshot;
+pub mod tank_movement;
+#[cfg(feature = "dev")]
+pub mod telemetry;
+
+/// The tank demo: player, tank movement and snapshots, with optional features.
+pub struct TanksDemoPlugin {
+    /// Whether tanks wrap around the screen edges.
+    pub screen_wrap: bool,
+
+    /// Whether the level plays music.
+    pub audio: bool,
+
+    /// The seed for gameplay randomness, or `None` to pick one at random.
+    pub rng_seed: Option<u64>,
+
+    /// Whether two local players share the keyboard, each with their own tank.
+    pub two_players: bool,
+
+    /// The radius of a round arena centered on the origin, or `None` for the usual rectangle.
+    pub circular_arena: Option<f32>,
+}
+
+impl Default for TanksDemoPlugin {
+    fn default() -> Self {
+        Self {
+            screen_wrap: true,
+            audio: true,
+            rng_seed: None,
+            two_players: false,
+            circular_arena: None,
+        }
+    }
+}
+
+impl TanksDemoPlugin {
+    /// Set whether tanks wrap around the screen edges.
+    pub fn with_screen_wrap(mut self, screen_wrap: bool) -> Self {
+        self.screen_wrap = screen_wrap;
+        self
+    }
+
+    /// Set whether the level plays music and sound effects.
+    pub fn with_audio(mut self, audio: bool) -> Self {
+        self.audio = audio;
+        self
+    }
+
+    /// Seed gameplay randomness, so runs with the same seed play out the same.
+    pub fn with_rng_seed(mut self, seed: u64) -> Self {
+        self.rng_seed = Some(seed);
+        self
+    }
+
+    /// Set whether two local players share the keyboard, each with their own tank.
+    /// The title menu's "Play" and "2 Players" buttons change this for the next game.
+    pub fn with_two_players(mut self, two_players: bool) -> Self {
+        self.two_players = two_players;
+        self
+    }
+
+    /// Keep tanks inside a round arena of `radius` centered on the origin, instead of wrapping.
+    pub fn with_circular_arena(mut self, radius: f32) -> Self {
+        self.circular_arena = Some(radius);
+        self
+    }
+}
+
+impl Plugin for TanksDemoPlugin {
+    fn build(&self, app: &mut App) {
+        // The demo's assets are loaded through `asset_tracking`, which the app may already have set up.
+        if !app.world().contains_resource::<ResourceHandles>() {
+            app.add_plugins(asset_tracking::plugin);
+        }
+
+        app.register_type::<DemoConfig>();
+        app.register_type::<ZLayers>();
+        app.init_resource::<ZLayers>();
+        app.insert_resource(DemoConfig {
+            screen_wrap: self.screen_wrap,
+            audio: self.audio,
+            two_players: self.two_players,
+        });
+        if let Some(radius) = self.circular_arena {
+            app.insert_resource(tank_movement::CircularArena {
+                center: Vec2::ZERO,
+                radius,
+            });
+        }
+        app.insert_resource(rng::GameRng::new(
+            self.rng_seed.unwrap_or_else(rand::random),
+        ));
+
+        app.add_plugins((
+            command_queue::plugin,
+            enemy::plugin,
+            free_camera::plugin,
+            input_command::plugin,
+            intro_camera::plugin,
+            level::plugin,
+            movement::plugin,
+            tank_movement::plugin,
+            player::plugin,
+            projectile::plugin,
+            rng::plugin,
+            snapshot::plugin,
+            #[cfg(feature = "dev")]
+            telemetry::plugin,
+        ));
+    }
+}
+
+/// The features the demo was configured with via [`TanksDemoPlugin`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct DemoConfig {
+    /// Whether tanks wrap around the screen edges.
+    pub screen_wrap: bool,
+
+    /// Whether the level plays music.
+    pub audio: bool,
+
+    /// Whether two local players share the keyboard, each with their own tank.
+    pub two_players: bool,
+}
+
+/// The z value of each kind of sprite, so they are drawn in a consistent order.
+/// Layers are listed from bottom to top.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct ZLayers {
+    /// Decals on the ground, such as scorch marks.
+    pub ground_decals: f32,
+
+    /// Shadows cast by tanks and other objects.
+    pub shadows: f32,
+
+    /// Tank chassis.
+    pub tanks: f32,
+
+    /// Turrets, drawn on top of their chassis.
+    pub turrets: f32,
+
+    /// Projectiles in flight.
+    pub projectiles: f32,
+
+    /// Visual effects such as explosions and smoke.
+    pub effects: f32,
+}
+
+impl Default for ZLayers {
+    fn default() -> Self {
+        Self {
+            ground_decals: 1.0,
+            shadows: 2.0,
+            tanks: 3.0,
+            turrets: 4.0,
+            projectiles: 5.0,
+            effects: 6.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        asset::AssetPlugin, audio::AudioPlugin, input::InputPlugin, state::app::StatesPlugin,
+    };
+
+    use std::any::TypeId;
+
+    use super::*;
+    use crate::demo::{
+        command_queue::CommandQueue,
+        enemy::EnemyController,
+        input_command::KeyBindings,
+        player::{PlayerAssets, TankCustomization, TurretController, player_tank_visual, tank},
+        projectile::{Projectile, projectile},
+        snapshot::RemoteTank,
+        tank_movement::{Disabled, ScreenWrap, TankMovementController, TankVisual},
+    };
+
+    #[test]
+    fn spawned_sprites_sit_on_their_z_layer() {
+        let mut app = App::new();
+        app.add_plugins(TransformPlugin);
+        // Layers that differ from the defaults, so nothing passes by accident
+        let z_layers = ZLayers {
+            ground_decals: 10.0,
+            shadows: 20.0,
+            tanks: 30.0,
+            turrets: 45.0,
+            projectiles: 50.0,
+            effects: 60.0,
+        };
+        let player_assets = PlayerAssets {
+            tank: Handle::default(),
+            turret: Handle::default(),
+            projectile: Handle::default(),
+            steps: Vec::new(),
+            engine: Handle::default(),
+        };
+        let tank = app
+            .world_mut()
+            .spawn(tank(
+                "Tank",
+                400.0,
+                Vec2::ZERO,
+                player_tank_visual(&player_assets),
+                &TankCustomization::default(),
+                &player_assets,
+                &z_layers,
+            ))
+            .id();
+        let projectile = app
+            .world_mut()
+            .spawn(projectile(
+                Vec2::ZERO,
+                Vec2::X,
+                Handle::default(),
+                &z_layers,
+            ))
+            .id();
+        app.update();
+
+        let world = app.world();
+        let z = |entity| {
+            world
+                .get::<GlobalTransform>(entity)
+                .unwrap()
+                .translation()
+                .z
+        };
+        let turret = world
+            .get::<Children>(tank)
+            .unwrap()
+            .iter()
+            .find(|&child| world.get::<TurretController>(child).is_some())
+            .unwrap();
+        assert_eq!(z(tank), z_layers.tanks);
+        assert_eq!(z(turret), z_layers.turrets);
+        assert_eq!(z(projectile), z_layers.projectiles);
+    }
+
+    #[test]
+    fn plugin_builds_in_a_minimal_app() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            AssetPlugin::default(),
+            ImagePlugin::default(),
+            AudioPlugin::default(),
+            InputPlugin,
+            TanksDemoPlugin::default()
+                .with_audio(false)
+                .with_rng_seed(7),
+        ));
+        crate::configure_system_sets(&mut app);
+
+        for _ in 0..3 {
+            app.update();
+        }
+
+        // The demo's modules registered their types for reflection
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        for type_id in [
+            TypeId::of::<TankMovementController>(),
+            TypeId::of::<TurretController>(),
+            TypeId::of::<ScreenWrap>(),
+            TypeId::of::<TankVisual>(),
+            TypeId::of::<Disabled>(),
+            TypeId::of::<Projectile>(),
+            TypeId::of::<EnemyController>(),
+            TypeId::of::<CommandQueue>(),
+            TypeId::of::<RemoteTank>(),
+            TypeId::of::<KeyBindings>(),
+            TypeId::of::<ZLayers>(),
+        ] {
+            assert!(registry.contains(type_id), "{type_id:?} isn't registered");
+        }
+        drop(registry);
+
+        assert_eq!(
+            *app.world().resource::<DemoConfig>(),
+            DemoConfig {
+                screen_wrap: true,
+                audio: false,
+                two_players: false,
+            }
+        );
+    }
 }
@@ -1,20 +1,63 @@
 //! Player-specific behavior.
 
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, TAU};
+
 use bevy::{
+    audio::Volume,
+    ecs::spawn::SpawnIter,
     image::{ImageLoaderSettings, ImageSampler},
     prelude::*,
+    window::PrimaryWindow,
+};
+
+use rand::{
+    Rng,
+    distributions::{Distribution, WeightedIndex},
 };
 
 use crate::{
-    AppSystems, PausableSystems,
+    AddGameplayTimers, AppSystems, PausableSystems, Pause,
     asset_tracking::LoadResource,
-    demo::tank_movement::{ScreenWrap, TankMovementController, record_tank_input},
+    audio::{SoundEffect, sound_effect},
+    demo::{
+        DemoConfig, ZLayers,
+        enemy::EnemyController,
+        input_command::{
+            GamepadDeadzone, InputCommandBuffer, InputContextStack, KeyBindingSet, KeyBindings,
+            PlayerKeys, QueueLiveInput, apply_input_commands, queue_input_command,
+            strongest_intent,
+        },
+        projectile::projectile,
+        rng::GameRng,
+        snapshot::RemoteTank,
+        tank_movement::{
+            Disabled, InvertControls, MaxFrameDelta, ScreenWrap, TankMovementController,
+            TankVisual, record_tank_input, shortest_angle_delta,
+        },
+    },
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Player>();
     app.register_type::<Turret>();
     app.register_type::<TurretController>();
+    app.register_type::<FireController>();
+    app.register_type::<ShotsFired>();
+    app.register_type::<TankCustomization>();
+    app.init_resource::<TankCustomization>();
+    app.register_type::<TurretSpring>();
+    app.register_type::<TurretIdleSway>();
+    app.register_type::<TankInput>();
+    app.register_type::<PlayerControls>();
+    app.register_type::<SampledPlayerInput>();
+    app.register_type::<FineAimModifier>();
+    app.init_resource::<FineAimModifier>();
+    app.register_type::<TurretSensitivity>();
+    app.init_resource::<TurretSensitivity>();
+    app.register_type::<TrackSounds>();
+    app.register_type::<EngineSound>();
+    app.register_type::<StepSoundWeights>();
+    app.init_resource::<StepSoundWeights>();
 
     app.register_type::<PlayerAssets>();
     app.load_resource::<PlayerAssets>();
@@ -25,53 +68,221 @@ pub(super) fn plugin(app: &mut App) {
         (
             record_tank_input,
             record_turret_input,
+            (queue_input_command, queue_player_controls).in_set(QueueLiveInput),
+            apply_input_commands,
+            apply_tank_input,
+            aim_turret_at_cursor,
             apply_turret_movement,
+            apply_turret_idle_sway,
         )
             .chain()
             .in_set(AppSystems::RecordInput)
             .in_set(PausableSystems),
     );
+
+    // Play track sounds while driving.
+    app.add_systems(
+        Update,
+        play_track_sounds
+            .run_if(resource_exists::<PlayerAssets>.and(audio_enabled))
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+
+    // Follow the tank's speed with its engine sound, and keep it quiet while paused.
+    app.add_observer(add_engine_sound);
+    app.add_systems(
+        Update,
+        update_engine_sounds
+            .run_if(audio_enabled)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+    app.add_systems(OnEnter(Pause(true)), pause_engine_sounds);
+    app.add_systems(OnExit(Pause(true)), resume_engine_sounds);
+
+    // Fire projectiles from the turret.
+    app.add_gameplay_timers(tick_fire_cooldowns);
+    app.add_systems(
+        Update,
+        fire_projectile
+            .run_if(resource_exists::<PlayerAssets>)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
 }
 
-/// The player character.
-pub fn player(max_speed: f32, player_assets: &PlayerAssets) -> impl Bundle {
+/// The player character, facing up.
+pub fn player(
+    max_speed: f32,
+    position: Vec2,
+    visual: TankVisual,
+    customization: &TankCustomization,
+    player_assets: &PlayerAssets,
+    z_layers: &ZLayers,
+) -> impl Bundle {
     (
-        Name::new("Player"),
+        tank(
+            "Player",
+            max_speed,
+            position,
+            visual,
+            customization,
+            player_assets,
+            z_layers,
+        ),
         Player,
+    )
+}
+
+/// A tank with a turret, facing up. It drives from whatever sets its [`TankMovementController`].
+pub fn tank(
+    name: &'static str,
+    max_speed: f32,
+    position: Vec2,
+    visual: TankVisual,
+    customization: &TankCustomization,
+    player_assets: &PlayerAssets,
+    z_layers: &ZLayers,
+) -> impl Bundle {
+    let hull_decal = customization
+        .decal
+        .clone()
+        .map(|decal| hull_decal(decal, z_layers));
+    let turret = turret(visual.clone(), customization, player_assets, z_layers);
+    (
+        Name::new(name),
         Sprite {
-            image: player_assets.tank.clone(),
+            image: visual.sprite.clone(),
+            color: customization.primary_color,
             ..default()
         },
-        Transform::from_scale(Vec2::splat(0.8).extend(1.0))
-            .with_rotation(Quat::from_rotation_z(f32::to_radians(90.0))),
+        Transform::from_translation(position.extend(z_layers.tanks))
+            .with_scale(Vec2::splat(visual.scale).extend(1.0))
+            // Rotate the sprite's front to face up
+            .with_rotation(Quat::from_rotation_z(
+                FRAC_PI_2 - visual.sprite_forward_offset,
+            )),
+        visual,
         TankMovementController {
             max_speed,
             ..default()
         },
         ScreenWrap,
-        children![turret(player_assets)],
+        ShotsFired::default(),
+        TrackSounds::default(),
+        Children::spawn((Spawn(turret), SpawnIter(hull_decal.into_iter()))),
     )
 }
 
+/// Give tanks with track sounds an engine sound too, unless audio is disabled.
+fn add_engine_sound(
+    trigger: Trigger<OnAdd, TrackSounds>,
+    config: Res<DemoConfig>,
+    player_assets: Option<Res<PlayerAssets>>,
+    mut commands: Commands,
+) {
+    let Some(player_assets) = player_assets.filter(|_| config.audio) else {
+        return;
+    };
+    commands.spawn((engine_sound(&player_assets), ChildOf(trigger.target())));
+}
+
+fn audio_enabled(config: Res<DemoConfig>) -> bool {
+    config.audio
+}
+
+/// The tank's looping engine sound, kept running for as long as the tank exists.
+fn engine_sound(player_assets: &PlayerAssets) -> impl Bundle {
+    (
+        Name::new("Engine Sound"),
+        AudioPlayer(player_assets.engine.clone()),
+        PlaybackSettings::LOOP.with_volume(Volume::Linear(ENGINE_IDLE_VOLUME)),
+        SoundEffect,
+        EngineSound,
+    )
+}
+
+/// The visual of the player's tank model, whose art faces right.
+pub fn player_tank_visual(player_assets: &PlayerAssets) -> TankVisual {
+    TankVisual {
+        sprite: player_assets.tank.clone(),
+        scale: 0.8,
+        sprite_forward_offset: 0.0,
+    }
+}
+
 /// The turret entity that sits on top of the tank.
-fn turret(player_assets: &PlayerAssets) -> impl Bundle {
+fn turret(
+    visual: TankVisual,
+    customization: &TankCustomization,
+    player_assets: &PlayerAssets,
+    z_layers: &ZLayers,
+) -> impl Bundle {
     (
         Name::new("Turret"),
         Turret,
         Sprite {
             image: player_assets.turret.clone(),
+            color: customization.secondary_color,
             ..default()
         },
-        Transform::from_scale(Vec2::splat(0.8).extend(1.0))
-            .with_rotation(Quat::from_rotation_z(f32::to_radians(0.))) // Add 80 degrees to point straight
-            .with_translation(Vec3::new(11.0, 0.0, 0.0)), // Move center to the right
+        Transform::from_scale(Vec2::splat(visual.scale).extend(1.0))
+            // Move center toward the front, and above the tank (z is relative to the parent)
+            .with_translation(
+                visual.local_forward() * TURRET_OFFSET
+                    + Vec3::Z * (z_layers.turrets - z_layers.tanks),
+            ),
         TurretController::default(),
+        FireController::default(),
     )
 }
 
+/// How far the turret's center sits ahead of the chassis center, in the chassis' local space.
+const TURRET_OFFSET: f32 = 11.0;
+
+/// A decal painted on the hull, between the chassis and the turret.
+fn hull_decal(decal: Handle<Image>, z_layers: &ZLayers) -> impl Bundle {
+    (
+        Name::new("Hull Decal"),
+        Sprite::from_image(decal),
+        Transform::from_xyz(0.0, 0.0, (z_layers.turrets - z_layers.tanks) / 2.0),
+    )
+}
+
+/// How the player wants their tank to look.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct TankCustomization {
+    /// The tint of the chassis sprite.
+    pub primary_color: Color,
+
+    /// The tint of the turret sprite.
+    pub secondary_color: Color,
+
+    /// An optional decal drawn on top of the hull.
+    pub decal: Option<Handle<Image>>,
+}
+
+impl Default for TankCustomization {
+    fn default() -> Self {
+        Self {
+            primary_color: Color::WHITE,
+            secondary_color: Color::WHITE,
+            decal: None,
+        }
+    }
+}
+
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
 #[reflect(Component)]
-struct Player;
+pub struct Player;
+
+/// Marks the audio entity playing a tank's engine loop.
+/// It's a child of the tank, whose speed sets its pitch and volume.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct EngineSound;
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
 #[reflect(Component)]
@@ -81,11 +292,19 @@ struct Turret;
 #[reflect(Component)]
 pub struct TurretController {
     /// The rotation intent (-1.0 to 1.0).
-    /// Positive values rotate clockwise, negative values rotate counter-clockwise.
+    /// Positive values rotate counter-clockwise (increasing Z rotation),
+    /// negative values rotate clockwise.
     pub rotation_intent: f32,
 
     /// The speed at which the turret rotates in radians per second.
     pub rotation_speed: f32,
+
+    /// The maximum rotation applied in a single frame in radians.
+    /// At very low frame rates this keeps the turret from over-rotating.
+    pub max_rotation_step: f32,
+
+    /// How the turret is aimed. Can be changed at any time.
+    pub aim_mode: TurretAimMode,
 }
 
 impl Default for TurretController {
@@ -93,10 +312,317 @@ impl Default for TurretController {
         Self {
             rotation_intent: 0.0,
             rotation_speed: f32::to_radians(180.0), // 180 degrees per second
+            max_rotation_step: FRAC_PI_4,
+            aim_mode: TurretAimMode::default(),
+        }
+    }
+}
+
+/// How a turret is aimed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum TurretAimMode {
+    /// The rotation intent comes from input commands (arrow keys or gamepad).
+    #[default]
+    Keyboard,
+    /// The turret turns toward the mouse cursor at its rotation speed.
+    MouseFollow,
+}
+
+/// Fires projectiles from a turret's barrel, at most once per cooldown.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct FireController {
+    /// Whether the turret wants to fire. Firing repeats while this stays set.
+    pub fire_intent: bool,
+
+    /// The minimum time between two shots.
+    pub cooldown: Timer,
+
+    /// The speed of fired projectiles in world units per second.
+    pub projectile_speed: f32,
+}
+
+impl Default for FireController {
+    fn default() -> Self {
+        let mut cooldown = Timer::from_seconds(0.4, TimerMode::Once);
+        // Start out ready to fire
+        cooldown.tick(cooldown.duration());
+        Self {
+            fire_intent: false,
+            cooldown,
+            projectile_speed: 900.0,
+        }
+    }
+}
+
+/// How many shots a tank's turret has fired.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct ShotsFired(pub u32);
+
+/// Makes a turret lag behind its input and overshoot slightly before settling,
+/// like a heavy barrel on a spring. Off unless added to a turret.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct TurretSpring {
+    /// How strongly the turret is pulled toward the target angle.
+    pub stiffness: f32,
+
+    /// How strongly the turret's angular velocity is damped.
+    /// Below `2 * stiffness.sqrt()` the spring is underdamped and overshoots.
+    pub damping: f32,
+
+    /// The angle the input is asking for in radians.
+    pub target: f32,
+
+    /// The current angular velocity in radians per second.
+    pub velocity: f32,
+}
+
+impl Default for TurretSpring {
+    fn default() -> Self {
+        Self {
+            stiffness: 60.0,
+            damping: 8.0,
+            target: 0.0,
+            velocity: 0.0,
+        }
+    }
+}
+
+/// Gently sways an idle turret back and forth, as if the gunner were scanning.
+/// Stops as soon as there is turret input or something else aims the turret (the mouse or an
+/// [`EnemyController`]), and picks up again from wherever the turret ends up.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct TurretIdleSway {
+    /// The largest offset from the resting angle in radians.
+    pub amplitude: f32,
+
+    /// The time for one full sway in seconds.
+    pub period: f32,
+
+    /// How long the turret has been idle in seconds.
+    pub elapsed: f32,
+
+    /// The offset currently applied to the turret in radians.
+    pub offset: f32,
+}
+
+impl Default for TurretIdleSway {
+    fn default() -> Self {
+        Self {
+            amplitude: f32::to_radians(3.0),
+            period: 4.0,
+            elapsed: 0.0,
+            offset: 0.0,
+        }
+    }
+}
+
+/// A modifier key that slows turret rotation down for fine aiming.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct FineAimModifier {
+    /// The key to hold for fine aiming.
+    pub key: KeyCode,
+
+    /// The fraction of the turret's rotation speed used while the key is held.
+    pub factor: f32,
+}
+
+impl Default for FineAimModifier {
+    fn default() -> Self {
+        Self {
+            key: KeyCode::ShiftLeft,
+            factor: 0.25,
         }
     }
 }
 
+/// Per-device multipliers on the turret rotation intent, so each input device can feel right.
+/// Values above 1.0 reach full speed with less stick travel, but the turret never turns faster
+/// than its `rotation_speed`. Negative values count as 0.0.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct TurretSensitivity {
+    /// The multiplier for keyboard (arrow key) aiming.
+    pub keyboard: f32,
+
+    /// The multiplier for gamepad stick aiming.
+    pub gamepad: f32,
+
+    /// The multiplier for mouse aiming.
+    pub mouse: f32,
+}
+
+impl Default for TurretSensitivity {
+    fn default() -> Self {
+        Self {
+            keyboard: 1.0,
+            gamepad: 1.0,
+            mouse: 1.0,
+        }
+    }
+}
+
+impl TurretSensitivity {
+    /// A keyboard rotation intent with the keyboard multiplier applied.
+    pub fn keyboard_intent(&self, intent: f32) -> f32 {
+        scale_intent(intent, self.keyboard)
+    }
+
+    /// A gamepad rotation intent with the gamepad multiplier applied.
+    pub fn gamepad_intent(&self, intent: f32) -> f32 {
+        scale_intent(intent, self.gamepad)
+    }
+
+    /// A mouse rotation intent limited by the mouse multiplier.
+    /// Mouse aim already turns just enough to reach the cursor, so the multiplier only caps its speed.
+    pub fn mouse_intent(&self, intent: f32) -> f32 {
+        let limit = self.mouse.clamp(0.0, 1.0);
+        intent.clamp(-limit, limit)
+    }
+}
+
+/// `intent` scaled by `multiplier`, kept within the -1.0 to 1.0 intent range.
+fn scale_intent(intent: f32, multiplier: f32) -> f32 {
+    (intent * multiplier.max(0.0)).clamp(-1.0, 1.0)
+}
+
+/// Plays [`PlayerAssets::steps`] as track sounds while the tank drives.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct TrackSounds {
+    /// Time between two track sounds at full speed.
+    pub interval: Timer,
+
+    /// The index of the last sound played, so it isn't played twice in a row.
+    pub last: Option<usize>,
+}
+
+impl Default for TrackSounds {
+    fn default() -> Self {
+        Self {
+            interval: Timer::from_seconds(0.3, TimerMode::Repeating),
+            last: None,
+        }
+    }
+}
+
+/// How track sounds are picked from [`PlayerAssets::steps`].
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct StepSoundWeights {
+    /// The relative weight of each step sound, in the same order as the sounds.
+    /// Missing weights count as 1.0.
+    pub weights: Vec<f32>,
+
+    /// The maximum random change in pitch, as a fraction (0.05 is ±5%).
+    pub pitch_jitter: f32,
+}
+
+impl Default for StepSoundWeights {
+    fn default() -> Self {
+        Self {
+            weights: vec![1.0; 4],
+            pitch_jitter: 0.05,
+        }
+    }
+}
+
+impl StepSoundWeights {
+    /// Pick the index of the next of `count` sounds, never repeating `last`
+    /// unless it's the only option.
+    pub fn pick(&self, count: usize, last: Option<usize>, rng: &mut impl Rng) -> Option<usize> {
+        let weights = |allow_last: bool| {
+            (0..count).map(move |i| {
+                if !allow_last && Some(i) == last {
+                    0.0
+                } else {
+                    self.weights.get(i).copied().unwrap_or(1.0).max(0.0)
+                }
+            })
+        };
+        let distribution =
+            WeightedIndex::new(weights(false)).or_else(|_| WeightedIndex::new(weights(true)));
+        distribution
+            .ok()
+            .map(|distribution| distribution.sample(rng))
+    }
+}
+
+/// Input for a tank provided by an external source (AI server, network, ...).
+///
+/// When present, this overrides the keyboard-derived intents of the tank and
+/// its turret. A single local player simply doesn't have this component.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct TankInput {
+    /// The forward/backward movement intent (-1.0 to 1.0).
+    pub forward: f32,
+
+    /// The chassis rotation intent (-1.0 to 1.0).
+    pub rotation: f32,
+
+    /// The world-space heading to steer toward in radians, for arcade-style controls.
+    /// When set, this replaces the rotation intent.
+    pub heading: Option<f32>,
+
+    /// The turret rotation intent (-1.0 to 1.0).
+    pub turret_rotation: f32,
+
+    /// Whether the tank wants to fire.
+    pub fire: bool,
+}
+
+/// Per-tank controls for local multiplayer, so players sharing a keyboard
+/// each drive their own tank. They're recorded into the tank's [`TankInput`],
+/// with the same control scheme, sensitivity and inversion settings as a single player.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+#[require(TankInput, SampledPlayerInput)]
+pub struct PlayerControls {
+    /// Which of the [`KeyBindings`] the player uses.
+    pub bindings: KeyBindingSet,
+
+    /// The key to hold for fine aiming, at the [`FineAimModifier`] factor.
+    pub fine_aim: KeyCode,
+
+    /// Which connected gamepad the player uses, counting from 0 in connection order.
+    pub gamepad: usize,
+}
+
+impl PlayerControls {
+    /// Player one: [`KeyBindings::player_one`] and left Shift to aim finely.
+    /// Uses the first gamepad.
+    pub const PLAYER_ONE: Self = Self {
+        bindings: KeyBindingSet::PlayerOne,
+        fine_aim: KeyCode::ShiftLeft,
+        gamepad: 0,
+    };
+
+    /// Player two: [`KeyBindings::player_two`] and right Shift to aim finely.
+    /// Uses the second gamepad.
+    pub const PLAYER_TWO: Self = Self {
+        bindings: KeyBindingSet::PlayerTwo,
+        fine_aim: KeyCode::ShiftRight,
+        gamepad: 1,
+    };
+
+    /// The player's gamepad, if it's connected.
+    pub fn find_gamepad<'a>(&self, gamepads: &'a Query<&Gamepad>) -> Option<&'a Gamepad> {
+        // Gamepad entities are spawned as they connect
+        gamepads.iter().sort::<Entity>().nth(self.gamepad)
+    }
+}
+
+/// What a [`PlayerControls`] tank's keys and gamepad asked for this tick.
+/// It's passed on to the tank's [`TankInput`] along with the live input command.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct SampledPlayerInput(pub TankInput);
+
 #[derive(Resource, Asset, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct PlayerAssets {
@@ -105,7 +631,11 @@ pub struct PlayerAssets {
     #[dependency]
     pub turret: Handle<Image>,
     #[dependency]
+    pub projectile: Handle<Image>,
+    #[dependency]
     pub steps: Vec<Handle<AudioSource>>,
+    #[dependency]
+    pub engine: Handle<AudioSource>,
 }
 
 impl FromWorld for PlayerAssets {
@@ -126,45 +656,1016 @@ impl FromWorld for PlayerAssets {
                     settings.sampler = ImageSampler::nearest();
                 },
             ),
+            projectile: assets.load_with_settings(
+                "images/projectile.png",
+                |settings: &mut ImageLoaderSettings| {
+                    // Use `nearest` image sampling to preserve pixel art style.
+                    settings.sampler = ImageSampler::nearest();
+                },
+            ),
             steps: vec![
                 assets.load("audio/sound_effects/step1.ogg"),
                 assets.load("audio/sound_effects/step2.ogg"),
                 assets.load("audio/sound_effects/step3.ogg"),
                 assets.load("audio/sound_effects/step4.ogg"),
             ],
+            engine: assets.load("audio/sound_effects/engine.wav"),
+        }
+    }
+}
+
+fn play_track_sounds(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_assets: Res<PlayerAssets>,
+    weights: Res<StepSoundWeights>,
+    mut rng: ResMut<GameRng>,
+    mut tank_query: Query<(&mut TrackSounds, &TankMovementController)>,
+) {
+    for (mut track_sounds, controller) in &mut tank_query {
+        // Rattle faster the faster the tank goes
+        let speed = if controller.max_speed > 0.0 {
+            controller.current_speed.abs() / controller.max_speed
+        } else {
+            0.0
+        };
+        if speed == 0.0 {
+            continue;
+        }
+        if !track_sounds
+            .interval
+            .tick(time.delta().mul_f32(speed))
+            .just_finished()
+        {
+            continue;
         }
+
+        let Some(index) = weights.pick(player_assets.steps.len(), track_sounds.last, &mut *rng)
+        else {
+            continue;
+        };
+        track_sounds.last = Some(index);
+
+        let jitter = weights.pitch_jitter;
+        let pitch = if jitter > 0.0 {
+            1.0 + rng.gen_range(-jitter..=jitter)
+        } else {
+            1.0
+        };
+        commands
+            .spawn(sound_effect(player_assets.steps[index].clone()))
+            .insert(PlaybackSettings::DESPAWN.with_speed(pitch));
     }
 }
 
-/// System to record turret input from keyboard.
+/// System to record turret input from keyboard and gamepad into the sampled input command,
+/// and each [`PlayerControls`] tank's own keys and gamepad into its [`SampledPlayerInput`].
 pub fn record_turret_input(
     input: Res<ButtonInput<KeyCode>>,
-    mut turret_query: Query<&mut TurretController>,
+    bindings: Res<KeyBindings>,
+    gamepads: Query<&Gamepad>,
+    deadzone: Res<GamepadDeadzone>,
+    fine_aim: Res<FineAimModifier>,
+    sensitivity: Res<TurretSensitivity>,
+    invert: Res<InvertControls>,
+    contexts: Res<InputContextStack>,
+    mut buffer: ResMut<InputCommandBuffer>,
+    mut player_query: Query<(&PlayerControls, &mut SampledPlayerInput)>,
 ) {
-    // Collect turret rotation input (Left/Right arrow keys)
-    let mut rotation_intent = 0.0;
-    if input.pressed(KeyCode::ArrowLeft) {
-        rotation_intent += 1.0; // Counter-clockwise
+    let gameplay = contexts.is_gameplay();
+    // The turret rotation and fire intents from `bindings` and a gamepad's right stick and buttons
+    let aim = |keys: &PlayerKeys, fine_aim_key: KeyCode, right_stick: Vec2, gamepad_fire| {
+        if !gameplay {
+            return (0.0, false);
+        }
+
+        // Collect turret rotation input (Left/Right arrow keys by default)
+        let mut rotation_intent = 0.0;
+        if input.pressed(keys.turret_left) {
+            rotation_intent += 1.0; // Counter-clockwise
+        }
+        if input.pressed(keys.turret_right) {
+            rotation_intent -= 1.0; // Clockwise
+        }
+        let rotation_intent = sensitivity.keyboard_intent(rotation_intent);
+
+        // Right stick X rotates the turret (pushing right turns clockwise)
+        let mut rotation_intent =
+            strongest_intent(rotation_intent, sensitivity.gamepad_intent(-right_stick.x));
+        if invert.turret {
+            rotation_intent = -rotation_intent;
+        }
+
+        // Slow down rotation while the fine aim modifier is held
+        if input.pressed(fine_aim_key) {
+            rotation_intent *= fine_aim.factor;
+        }
+
+        (rotation_intent, input.pressed(keys.fire) || gamepad_fire)
+    };
+
+    let command = &mut buffer.sampled;
+    let right_stick = deadzone.stick(&gamepads, Gamepad::right_stick);
+    let gamepad_fire = gamepads.iter().any(gamepad_fire_pressed);
+    (command.turret_rotation, command.fire) = aim(
+        &bindings.single_player,
+        fine_aim.key,
+        right_stick,
+        gamepad_fire,
+    );
+
+    // Each local player aims with their own keys and gamepad
+    for (controls, mut sampled) in &mut player_query {
+        let gamepad = controls.find_gamepad(&gamepads);
+        let right_stick = gamepad.map_or(Vec2::ZERO, |gamepad| {
+            deadzone.gamepad_stick(gamepad, Gamepad::right_stick)
+        });
+        let gamepad_fire = gamepad.is_some_and(gamepad_fire_pressed);
+        let sampled = &mut sampled.0;
+        (sampled.turret_rotation, sampled.fire) = aim(
+            bindings.keys(controls.bindings),
+            controls.fine_aim,
+            right_stick,
+            gamepad_fire,
+        );
     }
-    if input.pressed(KeyCode::ArrowRight) {
-        rotation_intent -= 1.0; // Clockwise
+}
+
+fn gamepad_fire_pressed(gamepad: &Gamepad) -> bool {
+    gamepad.pressed(GamepadButton::RightTrigger2) || gamepad.pressed(GamepadButton::South)
+}
+
+/// System to pass what each [`PlayerControls`] tank sampled this tick on to its [`TankInput`].
+fn queue_player_controls(mut tank_query: Query<(&SampledPlayerInput, &mut TankInput)>) {
+    for (sampled, mut tank_input) in &mut tank_query {
+        *tank_input = sampled.0;
     }
+}
+
+/// System to override keyboard-derived intents with [`TankInput`] where present.
+pub fn apply_tank_input(
+    mut tank_query: Query<(&TankInput, &mut TankMovementController, Option<&Children>)>,
+    mut turret_query: Query<(&mut TurretController, Option<&mut FireController>)>,
+) {
+    for (input, mut controller, children) in &mut tank_query {
+        controller.forward_intent = input.forward;
+        controller.rotation_intent = input.rotation;
+        controller.heading_intent = input.heading;
 
-    // Apply input to all turret controllers
-    for mut controller in &mut turret_query {
-        controller.rotation_intent = rotation_intent;
+        // The turret is a child of the tank.
+        let Some(children) = children else {
+            continue;
+        };
+        let mut turrets = turret_query.iter_many_mut(children);
+        while let Some((mut turret, fire_controller)) = turrets.fetch_next() {
+            turret.rotation_intent = input.turret_rotation;
+            if let Some(mut fire_controller) = fire_controller {
+                fire_controller.fire_intent = input.fire;
+            }
+        }
+    }
+}
+
+/// System to turn turrets in [`TurretAimMode::MouseFollow`] toward the cursor.
+pub fn aim_turret_at_cursor(
+    time: Res<Time>,
+    max_delta: Res<MaxFrameDelta>,
+    sensitivity: Res<TurretSensitivity>,
+    contexts: Res<InputContextStack>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mut turret_query: Query<(
+        &mut TurretController,
+        &Transform,
+        &GlobalTransform,
+        Option<&ChildOf>,
+    )>,
+    parent_query: Query<(&GlobalTransform, Option<&TankVisual>)>,
+) {
+    if !contexts.is_gameplay() {
+        return;
+    }
+    let (Ok(window), Ok((camera, camera_transform))) = (window.single(), camera.single()) else {
+        return;
+    };
+    let Some(cursor) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    else {
+        return;
+    };
+
+    let dt = max_delta.delta_secs(&time);
+    for (mut controller, transform, global_transform, parent) in &mut turret_query {
+        if controller.aim_mode != TurretAimMode::MouseFollow {
+            continue;
+        }
+
+        // The turret is a child of the tank, so aim relative to the tank's rotation,
+        // and the barrel points the same way as the tank's art
+        let (parent_angle, forward_offset) = parent
+            .and_then(|parent| parent_query.get(parent.parent()).ok())
+            .map_or((0.0, 0.0), |(parent, visual)| {
+                (
+                    parent.rotation().to_euler(EulerRot::XYZ).2,
+                    visual.map_or(0.0, |visual| visual.sprite_forward_offset),
+                )
+            });
+        let target_angle = (cursor - global_transform.translation().xy()).to_angle()
+            - parent_angle
+            - forward_offset;
+        let current_angle = transform.rotation.to_euler(EulerRot::XYZ).2;
+        let difference = shortest_angle_delta(current_angle, target_angle);
+
+        // Turn at the turret's rotation speed without overshooting the cursor
+        let max_rotation_delta = (controller.rotation_speed * dt).min(controller.max_rotation_step);
+        controller.rotation_intent = if max_rotation_delta > 0.0 {
+            sensitivity.mouse_intent(difference / max_rotation_delta)
+        } else {
+            0.0
+        };
     }
 }
 
 /// System to apply turret rotation based on controller input.
-fn apply_turret_movement(
+pub fn apply_turret_movement(
     time: Res<Time>,
-    mut turret_query: Query<(&TurretController, &mut Transform)>,
+    max_delta: Res<MaxFrameDelta>,
+    mut turret_query: Query<(
+        &TurretController,
+        &mut Transform,
+        Option<&mut TurretSpring>,
+        Option<&ChildOf>,
+    )>,
+    inactive_query: Query<(), Or<(With<Disabled>, With<RemoteTank>)>>,
+) {
+    let dt = max_delta.delta_secs(&time);
+    if dt == 0.0 {
+        return;
+    }
+
+    for (controller, mut transform, spring, parent) in &mut turret_query {
+        // A disabled tank's turret doesn't move, and a remote one follows its snapshots
+        if parent.is_some_and(|parent| inactive_query.contains(parent.parent())) {
+            continue;
+        }
+
+        // Apply rotation based on rotation intent, clamped per frame
+        let max_rotation_delta = (controller.rotation_speed * dt).min(controller.max_rotation_step);
+        let rotation_delta = controller.rotation_intent.clamp(-1.0, 1.0) * max_rotation_delta;
+
+        let Some(mut spring) = spring else {
+            transform.rotate_z(rotation_delta);
+            continue;
+        };
+
+        // Move the target instead, and let a damped spring pull the turret toward it
+        spring.target += rotation_delta;
+        let current = transform.rotation.to_euler(EulerRot::XYZ).2;
+        let offset = shortest_angle_delta(current, spring.target);
+        // Keep the target within half a turn so it never winds up
+        spring.target = current + offset;
+        let acceleration = spring.stiffness * offset - spring.damping * spring.velocity;
+        spring.velocity += acceleration * dt;
+        transform.rotate_z(
+            (spring.velocity * dt)
+                .clamp(-controller.max_rotation_step, controller.max_rotation_step),
+        );
+    }
+}
+
+fn apply_turret_idle_sway(
+    time: Res<Time>,
+    max_delta: Res<MaxFrameDelta>,
+    mut turret_query: Query<(
+        &TurretController,
+        &mut TurretIdleSway,
+        &mut Transform,
+        Option<&mut TurretSpring>,
+        Option<&ChildOf>,
+    )>,
+    inactive_query: Query<(), Or<(With<Disabled>, With<RemoteTank>, With<EnemyController>)>>,
+) {
+    let dt = max_delta.delta_secs(&time);
+    for (controller, mut sway, mut transform, spring, parent) in &mut turret_query {
+        // Any input, mouse or AI aim (or a disabled or remote tank) stops the sway where it is
+        if controller.rotation_intent != 0.0
+            || controller.aim_mode == TurretAimMode::MouseFollow
+            || parent.is_some_and(|parent| inactive_query.contains(parent.parent()))
+        {
+            sway.elapsed = 0.0;
+            sway.offset = 0.0;
+            continue;
+        }
+
+        sway.elapsed += dt;
+        let offset = sway.amplitude * (TAU * sway.elapsed / sway.period.max(f32::EPSILON)).sin();
+        let rotation_delta = offset - sway.offset;
+        sway.offset = offset;
+
+        // A sprung turret sways its target, so the spring doesn't pull it back
+        match spring {
+            Some(mut spring) => spring.target += rotation_delta,
+            None => transform.rotate_z(rotation_delta),
+        }
+    }
+}
+
+/// Distance from the turret's center to the end of its barrel, in turret sprite pixels.
+const MUZZLE_OFFSET: f32 = 50.0;
+
+fn tick_fire_cooldowns(time: Res<Time>, mut fire_query: Query<&mut FireController>) {
+    for mut fire_controller in &mut fire_query {
+        fire_controller.cooldown.tick(time.delta());
+    }
+}
+
+fn fire_projectile(
+    mut commands: Commands,
+    player_assets: Res<PlayerAssets>,
+    z_layers: Res<ZLayers>,
+    mut turret_query: Query<(&mut FireController, &GlobalTransform, Option<&ChildOf>)>,
+    disabled_query: Query<(), With<Disabled>>,
+    visual_query: Query<&TankVisual>,
+    mut shots_query: Query<&mut ShotsFired>,
+) {
+    for (mut fire_controller, global_transform, parent) in &mut turret_query {
+        if !fire_controller.fire_intent || !fire_controller.cooldown.finished() {
+            continue;
+        }
+        // A disabled tank's turret doesn't fire
+        if parent.is_some_and(|parent| disabled_query.contains(parent.parent())) {
+            continue;
+        }
+        fire_controller.cooldown.reset();
+        if let Some(mut shots) = parent.and_then(|parent| shots_query.get_mut(parent.parent()).ok())
+        {
+            shots.0 += 1;
+        }
+
+        // The turret is a child of the tank, so use its world transform.
+        // The barrel points the same way as the tank's art.
+        let local_forward = parent
+            .and_then(|parent| visual_query.get(parent.parent()).ok())
+            .map_or(Vec3::X, TankVisual::local_forward);
+        let direction = (global_transform.rotation() * local_forward)
+            .xy()
+            .normalize_or_zero();
+        let muzzle = global_transform
+            .transform_point(local_forward * MUZZLE_OFFSET)
+            .xy();
+        commands.spawn(projectile(
+            muzzle,
+            direction * fire_controller.projectile_speed,
+            player_assets.projectile.clone(),
+            &z_layers,
+        ));
+    }
+}
+
+// The engine loop's playback speed and volume when the tank stands still,
+// and at full speed forward or in reverse.
+const ENGINE_IDLE_SPEED: f32 = 0.6;
+const ENGINE_IDLE_VOLUME: f32 = 0.25;
+const ENGINE_FULL_SPEED: f32 = 1.4;
+const ENGINE_FULL_VOLUME: f32 = 0.6;
+
+fn update_engine_sounds(
+    global_volume: Res<GlobalVolume>,
+    tank_query: Query<(&TankMovementController, &Children)>,
+    mut engine_query: Query<(&mut PlaybackSettings, &mut AudioSink), With<EngineSound>>,
 ) {
-    for (controller, mut transform) in &mut turret_query {
-        // Apply rotation based on rotation intent
-        let rotation_delta =
-            controller.rotation_intent * controller.rotation_speed * time.delta_secs();
-        transform.rotate_z(rotation_delta);
+    for (controller, children) in &tank_query {
+        // Idle low rather than cutting out, and rev up with speed either way
+        let speed = if controller.max_speed > 0.0 {
+            (controller.current_speed.abs() / controller.max_speed).min(1.0)
+        } else {
+            0.0
+        };
+        let mut engines = engine_query.iter_many_mut(children);
+        while let Some((mut playback, mut sink)) = engines.fetch_next() {
+            sink.set_speed(ENGINE_IDLE_SPEED.lerp(ENGINE_FULL_SPEED, speed));
+            // Keep the playback volume in step, so `apply_global_volume` doesn't reset it
+            playback.volume = Volume::Linear(ENGINE_IDLE_VOLUME.lerp(ENGINE_FULL_VOLUME, speed));
+            sink.set_volume(global_volume.volume * playback.volume);
+        }
+    }
+}
+
+fn pause_engine_sounds(engine_query: Query<&AudioSink, With<EngineSound>>) {
+    for sink in &engine_query {
+        sink.pause();
+    }
+}
+
+fn resume_engine_sounds(engine_query: Query<&AudioSink, With<EngineSound>>) {
+    for sink in &engine_query {
+        sink.play();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{state::app::StatesPlugin, time::TimeUpdateStrategy};
+
+    use crate::demo::{
+        input_command::{InputAction, InputCommand},
+        projectile::Projectile,
+        tank_movement::ControlScheme,
+    };
+
+    use super::*;
+
+    /// Spawn a tank with a turret, returning both entities.
+    fn spawn_tank(world: &mut World, components: impl Bundle) -> (Entity, Entity) {
+        let tank = world
+            .spawn((TankMovementController::default(), components))
+            .id();
+        let turret = world
+            .spawn((
+                TurretController::default(),
+                FireController::default(),
+                ChildOf(tank),
+            ))
+            .id();
+        (tank, turret)
+    }
+
+    #[test]
+    fn step_sound_never_repeats_the_last_one() {
+        let weights = StepSoundWeights::default();
+        let mut rng = GameRng::new(1);
+        let mut last = None;
+        for _ in 0..100 {
+            let next = weights.pick(4, last, &mut rng);
+            assert!(next.is_some_and(|next| next < 4));
+            assert_ne!(next, last);
+            last = next;
+        }
+    }
+
+    #[test]
+    fn step_sound_skips_zero_weights() {
+        let weights = StepSoundWeights {
+            weights: vec![0.0, 1.0, 0.0],
+            ..default()
+        };
+        let mut rng = GameRng::new(2);
+        for _ in 0..20 {
+            assert_eq!(weights.pick(3, None, &mut rng), Some(1));
+        }
+    }
+
+    #[test]
+    fn step_sound_repeats_when_it_is_the_only_option() {
+        let weights = StepSoundWeights::default();
+        let mut rng = GameRng::new(3);
+        assert_eq!(weights.pick(1, Some(0), &mut rng), Some(0));
+        assert_eq!(weights.pick(0, None, &mut rng), None);
+    }
+
+    #[test]
+    fn step_sound_picks_are_reproducible_for_a_seed() {
+        let weights = StepSoundWeights::default();
+        let picks = |seed| {
+            let mut rng = GameRng::new(seed);
+            let mut last = None;
+            (0..16)
+                .map(|_| {
+                    last = weights.pick(4, last, &mut rng);
+                    last
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(picks(7), picks(7));
+    }
+
+    #[test]
+    fn tank_input_overrides_input_commands() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(InputCommandBuffer {
+            commands: [InputCommand {
+                forward: 1.0,
+                rotation: -1.0,
+                heading: Some(0.5),
+                turret_rotation: -1.0,
+                fire: false,
+            }]
+            .into(),
+            ..default()
+        });
+        app.add_systems(Update, (apply_input_commands, apply_tank_input).chain());
+
+        let world = app.world_mut();
+        let (external, external_turret) = spawn_tank(
+            world,
+            TankInput {
+                forward: -0.5,
+                rotation: 0.25,
+                heading: None,
+                turret_rotation: 0.75,
+                fire: true,
+            },
+        );
+        let (local, local_turret) = spawn_tank(world, ());
+        app.update();
+
+        let world = app.world();
+        let controller = world.get::<TankMovementController>(external).unwrap();
+        assert_eq!(controller.forward_intent, -0.5);
+        assert_eq!(controller.rotation_intent, 0.25);
+        assert_eq!(controller.heading_intent, None);
+        let turret = world.get::<TurretController>(external_turret).unwrap();
+        assert_eq!(turret.rotation_intent, 0.75);
+        assert!(
+            world
+                .get::<FireController>(external_turret)
+                .unwrap()
+                .fire_intent
+        );
+
+        // Tanks without `TankInput` still follow the shared command
+        let controller = world.get::<TankMovementController>(local).unwrap();
+        assert_eq!(controller.forward_intent, 1.0);
+        assert_eq!(controller.heading_intent, Some(0.5));
+        let turret = world.get::<TurretController>(local_turret).unwrap();
+        assert_eq!(turret.rotation_intent, -1.0);
+        assert!(
+            !world
+                .get::<FireController>(local_turret)
+                .unwrap()
+                .fire_intent
+        );
+    }
+
+    #[test]
+    fn player_controls_follow_the_shared_control_settings() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<KeyBindings>();
+        app.init_resource::<GamepadDeadzone>();
+        app.insert_resource(ControlScheme::Arcade);
+        app.insert_resource(InvertControls {
+            turret: true,
+            ..default()
+        });
+        app.init_resource::<FineAimModifier>();
+        app.init_resource::<TurretSensitivity>();
+        app.init_resource::<InputContextStack>();
+        app.init_resource::<InputCommandBuffer>();
+        app.add_systems(
+            Update,
+            (
+                record_tank_input,
+                record_turret_input,
+                queue_player_controls,
+            )
+                .chain(),
+        );
+        let player_two = app.world_mut().spawn(PlayerControls::PLAYER_TWO).id();
+
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.press(KeyCode::ArrowUp);
+        input.press(KeyCode::Comma);
+        input.press(KeyCode::ShiftRight);
+        app.update();
+
+        let tank_input = app.world().get::<TankInput>(player_two).unwrap();
+        assert_eq!(tank_input.forward, 1.0);
+        assert_eq!(tank_input.heading, Some(FRAC_PI_2));
+        // Inverted, and slowed down by fine aim
+        assert_eq!(tank_input.turret_rotation, -0.25);
+        // Player one's keys aren't player two's
+        let command = app.world().resource::<InputCommandBuffer>().sampled;
+        assert_eq!(command.forward, 0.0);
+        assert_eq!(command.turret_rotation, 0.0);
+    }
+
+    #[test]
+    fn player_controls_use_their_rebound_keys() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_resource::<ButtonInput<KeyCode>>();
+        let mut bindings = KeyBindings::default();
+        bindings.set(KeyBindingSet::PlayerTwo, InputAction::Fire, KeyCode::KeyK);
+        bindings.set(
+            KeyBindingSet::PlayerTwo,
+            InputAction::Forward,
+            KeyCode::KeyI,
+        );
+        app.insert_resource(bindings);
+        app.init_resource::<GamepadDeadzone>();
+        app.init_resource::<ControlScheme>();
+        app.init_resource::<InvertControls>();
+        app.init_resource::<FineAimModifier>();
+        app.init_resource::<TurretSensitivity>();
+        app.init_resource::<InputContextStack>();
+        app.init_resource::<InputCommandBuffer>();
+        app.add_systems(
+            Update,
+            (
+                record_tank_input,
+                record_turret_input,
+                queue_player_controls,
+            )
+                .chain(),
+        );
+        let player_one = app.world_mut().spawn(PlayerControls::PLAYER_ONE).id();
+        let player_two = app.world_mut().spawn(PlayerControls::PLAYER_TWO).id();
+
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.press(KeyCode::KeyI);
+        input.press(KeyCode::KeyK);
+        // Player two's old keys do nothing anymore
+        input.press(KeyCode::Slash);
+        app.update();
+
+        let tank_input = |tank| *app.world().get::<TankInput>(tank).unwrap();
+        assert_eq!(tank_input(player_two).forward, 1.0);
+        assert!(tank_input(player_two).fire);
+        assert_eq!(tank_input(player_one), TankInput::default());
+
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.release_all();
+        input.press(KeyCode::Slash);
+        app.update();
+        let tank_input = |tank| *app.world().get::<TankInput>(tank).unwrap();
+        assert!(!tank_input(player_two).fire);
+    }
+
+    #[test]
+    fn fine_aim_turns_the_turret_at_a_fraction_of_its_speed() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        app.init_resource::<MaxFrameDelta>();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<KeyBindings>();
+        app.init_resource::<GamepadDeadzone>();
+        app.init_resource::<InvertControls>();
+        app.insert_resource(FineAimModifier {
+            key: KeyCode::ControlLeft,
+            factor: 0.4,
+        });
+        app.init_resource::<TurretSensitivity>();
+        app.init_resource::<InputContextStack>();
+        app.init_resource::<InputCommandBuffer>();
+        app.add_systems(
+            Update,
+            (
+                record_turret_input,
+                queue_input_command,
+                apply_input_commands,
+                apply_turret_movement,
+            )
+                .chain(),
+        );
+        let turret = app
+            .world_mut()
+            .spawn((TurretController::default(), Transform::default()))
+            .id();
+        let turn = |app: &mut App, keys: &[KeyCode]| {
+            let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            input.release_all();
+            for &key in keys {
+                input.press(key);
+            }
+            let rotation = |app: &App| {
+                let transform = app.world().get::<Transform>(turret).unwrap();
+                transform.rotation.to_euler(EulerRot::XYZ).2
+            };
+            let before = rotation(app);
+            app.update();
+            rotation(app) - before
+        };
+
+        // The first update only starts the clock
+        turn(&mut app, &[]);
+        let controller = app.world().get::<TurretController>(turret).unwrap();
+        let max_rotation_delta =
+            (controller.rotation_speed * 0.1).min(controller.max_rotation_step);
+        let full = turn(&mut app, &[KeyCode::ArrowLeft]);
+        assert!((full - max_rotation_delta).abs() < 1e-5, "{full}");
+        let fine = turn(&mut app, &[KeyCode::ArrowLeft, KeyCode::ControlLeft]);
+        assert!((fine - 0.4 * max_rotation_delta).abs() < 1e-5, "{fine}");
+    }
+
+    #[test]
+    fn turret_rotation_per_frame_is_limited_to_max_rotation_step() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        app.init_resource::<MaxFrameDelta>();
+        app.add_systems(Update, apply_turret_movement);
+        let turret = app
+            .world_mut()
+            .spawn((
+                TurretController {
+                    rotation_intent: -1.0,
+                    // Would turn 100 radians in a frame without the limit
+                    rotation_speed: 1000.0,
+                    max_rotation_step: 0.2,
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        // The first update only starts the clock
+        app.update();
+        app.update();
+
+        let angle = app
+            .world()
+            .get::<Transform>(turret)
+            .unwrap()
+            .rotation
+            .to_euler(EulerRot::XYZ)
+            .2;
+        assert!((angle + 0.2).abs() < 1e-5, "{angle}");
+    }
+
+    #[test]
+    fn underdamped_spring_overshoots_then_settles() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            10,
+        )));
+        app.init_resource::<MaxFrameDelta>();
+        app.add_systems(Update, apply_turret_movement);
+        let spring = TurretSpring {
+            target: 1.0,
+            ..default()
+        };
+        assert!(spring.damping < 2.0 * spring.stiffness.sqrt());
+        let turret = app
+            .world_mut()
+            .spawn((TurretController::default(), spring, Transform::default()))
+            .id();
+        let angle = |app: &App| {
+            app.world()
+                .get::<Transform>(turret)
+                .unwrap()
+                .rotation
+                .to_euler(EulerRot::XYZ)
+                .2
+        };
+
+        let mut max_angle = 0.0_f32;
+        for _ in 0..300 {
+            app.update();
+            max_angle = max_angle.max(angle(&app));
+        }
+        assert!(max_angle > 1.01, "{max_angle}");
+        let angle = angle(&app);
+        assert!((angle - 1.0).abs() < 1e-3, "{angle}");
+    }
+
+    /// The angle of an idle swaying turret after each of `frames` updates.
+    fn sway_angles(frames: usize, tank_components: impl Bundle) -> Vec<f32> {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            50,
+        )));
+        app.init_resource::<MaxFrameDelta>();
+        app.add_systems(Update, apply_turret_idle_sway);
+        let (_, turret) = spawn_tank(app.world_mut(), tank_components);
+        app.world_mut().entity_mut(turret).insert((
+            TurretIdleSway {
+                amplitude: 0.1,
+                period: 1.0,
+                ..default()
+            },
+            Transform::default(),
+        ));
+        (0..frames)
+            .map(|_| {
+                app.update();
+                app.world()
+                    .get::<Transform>(turret)
+                    .unwrap()
+                    .rotation
+                    .to_euler(EulerRot::XYZ)
+                    .2
+            })
+            .collect()
+    }
+
+    #[test]
+    fn idle_sway_stays_within_its_amplitude() {
+        let angles = sway_angles(60, ());
+        assert!(angles.iter().all(|angle| angle.abs() <= 0.1 + 1e-5));
+        // It swings both ways, close to the full amplitude
+        let max = angles.iter().copied().fold(f32::MIN, f32::max);
+        let min = angles.iter().copied().fold(f32::MAX, f32::min);
+        assert!(max > 0.09 && min < -0.09, "{min}..{max}");
+    }
+
+    #[test]
+    fn enemy_turrets_dont_sway() {
+        let angles = sway_angles(20, EnemyController::default());
+        assert!(angles.iter().all(|&angle| angle == 0.0));
+    }
+
+    #[test]
+    fn tank_spawns_with_its_customization() {
+        let mut world = World::new();
+        let player_assets = PlayerAssets {
+            tank: Handle::default(),
+            turret: Handle::default(),
+            projectile: Handle::default(),
+            steps: Vec::new(),
+            engine: Handle::default(),
+        };
+        let decal: Handle<Image> =
+            bevy::asset::weak_handle!("6f0c4a8e-2d13-4b0e-9a57-0a3f3c1d2130");
+        let customization = TankCustomization {
+            primary_color: Color::srgb(1.0, 0.0, 0.0),
+            secondary_color: Color::srgb(0.0, 0.0, 1.0),
+            decal: Some(decal.clone()),
+        };
+        let tank = world
+            .spawn(super::tank(
+                "Tank",
+                400.0,
+                Vec2::ZERO,
+                player_tank_visual(&player_assets),
+                &customization,
+                &player_assets,
+                &ZLayers::default(),
+            ))
+            .id();
+
+        assert_eq!(
+            world.get::<Sprite>(tank).unwrap().color,
+            customization.primary_color
+        );
+        let children = world.get::<Children>(tank).unwrap();
+        let turret = children
+            .iter()
+            .find(|&child| world.get::<Turret>(child).is_some())
+            .unwrap();
+        assert_eq!(
+            world.get::<Sprite>(turret).unwrap().color,
+            customization.secondary_color
+        );
+        assert!(
+            children
+                .iter()
+                .any(|child| world.get::<Sprite>(child).unwrap().image == decal)
+        );
+    }
+
+    #[test]
+    fn pausing_halts_fire_cooldowns() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        crate::configure_system_sets(&mut app);
+        app.add_gameplay_timers(tick_fire_cooldowns);
+        let mut fire_controller = FireController::default();
+        fire_controller.cooldown.reset();
+        let turret = app.world_mut().spawn(fire_controller).id();
+        let elapsed = |app: &App| {
+            app.world()
+                .get::<FireController>(turret)
+                .unwrap()
+                .cooldown
+                .elapsed()
+        };
+
+        // The first update only starts the clock
+        app.update();
+        app.update();
+        assert_eq!(elapsed(&app), Duration::from_millis(100));
+
+        app.world_mut()
+            .resource_mut::<NextState<Pause>>()
+            .set(Pause(true));
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(elapsed(&app), Duration::from_millis(100));
+
+        app.world_mut()
+            .resource_mut::<NextState<Pause>>()
+            .set(Pause(false));
+        app.update();
+        app.update();
+        assert!(elapsed(&app) > Duration::from_millis(100));
+    }
+
+    #[test]
+    fn sensitivity_keeps_intents_in_range() {
+        let sensitivity = TurretSensitivity {
+            keyboard: 2.0,
+            gamepad: 1.5,
+            mouse: 4.0,
+        };
+        assert_eq!(sensitivity.keyboard_intent(1.0), 1.0);
+        assert_eq!(sensitivity.keyboard_intent(-1.0), -1.0);
+        assert_eq!(sensitivity.gamepad_intent(0.5), 0.75);
+        assert_eq!(sensitivity.mouse_intent(3.0), 1.0);
+        assert_eq!(sensitivity.mouse_intent(-0.25), -0.25);
+    }
+
+    #[test]
+    fn negative_sensitivity_counts_as_zero() {
+        let sensitivity = TurretSensitivity {
+            keyboard: -1.0,
+            gamepad: -0.5,
+            mouse: -2.0,
+        };
+        assert_eq!(sensitivity.keyboard_intent(1.0), 0.0);
+        assert_eq!(sensitivity.gamepad_intent(-1.0), 0.0);
+        assert_eq!(sensitivity.mouse_intent(1.0), 0.0);
+    }
+
+    #[test]
+    fn turret_fires_along_the_tank_art_forward() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_resource::<ZLayers>();
+        app.insert_resource(PlayerAssets {
+            tank: Handle::default(),
+            turret: Handle::default(),
+            projectile: Handle::default(),
+            steps: Vec::new(),
+            engine: Handle::default(),
+        });
+        app.add_systems(Update, fire_projectile);
+        // Art facing up, so an unrotated turret points up
+        let (tank, turret) = spawn_tank(
+            app.world_mut(),
+            (
+                TankVisual {
+                    sprite: Handle::default(),
+                    scale: 1.0,
+                    sprite_forward_offset: FRAC_PI_2,
+                },
+                ShotsFired::default(),
+            ),
+        );
+        app.world_mut().entity_mut(turret).insert((
+            GlobalTransform::IDENTITY,
+            FireController {
+                fire_intent: true,
+                ..default()
+            },
+        ));
+        app.update();
+
+        let mut projectiles = app.world_mut().query::<(&Projectile, &Transform)>();
+        let (projectile, transform) = projectiles.single(app.world()).unwrap();
+        assert!(projectile.velocity.normalize().abs_diff_eq(Vec2::Y, 1e-5));
+        assert!(
+            transform
+                .translation
+                .xy()
+                .abs_diff_eq(Vec2::Y * MUZZLE_OFFSET, 1e-3)
+        );
+        assert_eq!(app.world().get::<ShotsFired>(tank), Some(&ShotsFired(1)));
+    }
+
+    fn engine_sound_count(audio: bool) -> usize {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(DemoConfig {
+            screen_wrap: true,
+            audio,
+            two_players: false,
+        });
+        app.insert_resource(PlayerAssets {
+            tank: Handle::default(),
+            turret: Handle::default(),
+            projectile: Handle::default(),
+            steps: Vec::new(),
+            engine: Handle::default(),
+        });
+        app.add_observer(add_engine_sound);
+        app.world_mut().spawn(TrackSounds::default());
+        app.update();
+
+        let mut engines = app
+            .world_mut()
+            .query_filtered::<&ChildOf, With<EngineSound>>();
+        engines.iter(app.world()).count()
+    }
+
+    #[test]
+    fn engine_sound_follows_the_audio_setting() {
+        assert_eq!(engine_sound_count(true), 1);
+        assert_eq!(engine_sound_count(false), 0);
     }
 }
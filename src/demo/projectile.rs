@@ -0,0 +1,77 @@
+//! Projectiles fired by tanks. They fly in a straight line and are despawned
+//! once they leave the area tanks wrap around in.
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        ZLayers,
+        tank_movement::{ArenaBounds, MaxFrameDelta, wrap_area_size},
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Projectile>();
+
+    app.add_systems(
+        Update,
+        (advance_projectiles, despawn_offscreen_projectiles)
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// A projectile in flight.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Projectile {
+    /// The velocity in world units per second.
+    pub velocity: Vec2,
+}
+
+/// A projectile at `position` flying with `velocity`, facing where it flies.
+pub fn projectile(
+    position: Vec2,
+    velocity: Vec2,
+    image: Handle<Image>,
+    z_layers: &ZLayers,
+) -> impl Bundle {
+    (
+        Name::new("Projectile"),
+        Projectile { velocity },
+        Sprite::from_image(image),
+        Transform::from_translation(position.extend(z_layers.projectiles))
+            .with_rotation(Quat::from_rotation_z(velocity.to_angle()))
+            .with_scale(Vec2::splat(2.0).extend(1.0)),
+        StateScoped(Screen::Gameplay),
+    )
+}
+
+fn advance_projectiles(
+    time: Res<Time>,
+    max_delta: Res<MaxFrameDelta>,
+    mut projectile_query: Query<(&Projectile, &mut Transform)>,
+) {
+    let dt = max_delta.delta_secs(&time);
+    for (projectile, mut transform) in &mut projectile_query {
+        transform.translation += (projectile.velocity * dt).extend(0.0);
+    }
+}
+
+fn despawn_offscreen_projectiles(
+    mut commands: Commands,
+    window: Query<&Window, With<PrimaryWindow>>,
+    arena: Res<ArenaBounds>,
+    projectile_query: Query<(Entity, &Transform), With<Projectile>>,
+) {
+    let half_size = wrap_area_size(window.single().ok().map(Window::size), &arena) / 2.0;
+    for (entity, transform) in &projectile_query {
+        let position = transform.translation.xy();
+        if position.abs().cmpgt(half_size).any() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
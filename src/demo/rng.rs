@@ -0,0 +1,100 @@
+//! Seedable randomness for gameplay, so that runs can be reproduced.
+//!
+//! All gameplay randomness (spawn positions, spread, pitch variation, ...)
+//! should go through [`GameRng`] rather than `rand::random`.
+
+use bevy::prelude::*;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{demo::level::spawn_level, screens::Screen};
+
+pub(super) fn plugin(app: &mut App) {
+    // Restart the sequence for each level, so the same seed plays out the same way.
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        reset_game_rng.before(spawn_level),
+    );
+}
+
+/// The random number generator for gameplay.
+///
+/// Use it through the [`rand::Rng`] trait, e.g. `rng.gen_range(0.0..1.0)`.
+/// It's backed by ChaCha8, whose output for a seed doesn't change between `rand` versions.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    rng: ChaCha8Rng,
+}
+
+impl GameRng {
+    /// Create a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// The seed the generator was last seeded with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Restart the generator from a seed, e.g. to replay a recorded run.
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Self::new(seed);
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+fn reset_game_rng(mut rng: ResMut<GameRng>) {
+    let seed = rng.seed();
+    rng.reseed(seed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_produces_a_fixed_sequence() {
+        let mut rng = GameRng::new(42);
+        let values: Vec<u64> = (0..3).map(|_| rng.next_u64()).collect();
+        assert_eq!(
+            values,
+            vec![
+                12578764544318200737,
+                17529487244874322312,
+                7886285670807131020
+            ]
+        );
+    }
+
+    #[test]
+    fn reseed_restarts_the_sequence() {
+        let mut rng = GameRng::new(42);
+        let first = rng.next_u64();
+        rng.next_u64();
+        rng.reseed(42);
+        assert_eq!(rng.next_u64(), first);
+        assert_eq!(rng.seed(), 42);
+    }
+}
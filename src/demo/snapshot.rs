@@ -0,0 +1,309 @@
+//! Serializable snapshots of tank state, as groundwork for multiplayer.
+//!
+//! There is no transport here, only the data layer: [`capture_snapshot`] turns
+//! a tank into a [`TankSnapshot`], local tanks with a [`Replicated`] component
+//! keep an up-to-date snapshot ready to be sent, and tanks with a
+//! [`RemoteTank`] component are interpolated between their most recent
+//! snapshots, slightly in the past, to hide latency.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        player::TurretController,
+        tank_movement::{apply_tank_movement, shortest_angle_delta},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TankSnapshot>();
+    app.register_type::<Replicated>();
+    app.register_type::<RemoteTank>();
+
+    // Capture and interpolate after movement, so the snapshot is the frame's final state
+    app.add_systems(
+        Update,
+        (capture_snapshots, apply_snapshot)
+            .after(apply_tank_movement)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// The replicated state of a single tank.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+pub struct TankSnapshot {
+    /// Seconds since startup on the capturing peer.
+    pub time: f32,
+
+    /// World position of the tank.
+    pub position: Vec2,
+
+    /// Rotation of the tank chassis around the Z axis in radians.
+    pub rotation: f32,
+
+    /// Rotation of the turret relative to the chassis in radians.
+    pub turret_rotation: f32,
+}
+
+/// Captures the state of a tank at `time` from its own transform and that of its turret.
+pub fn capture_snapshot(time: f32, tank: &Transform, turret: Option<&Transform>) -> TankSnapshot {
+    TankSnapshot {
+        time,
+        position: tank.translation.xy(),
+        rotation: z_rotation(tank),
+        turret_rotation: turret.map(z_rotation).unwrap_or_default(),
+    }
+}
+
+/// A local tank whose state should be replicated to other peers.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Replicated {
+    /// The snapshot captured this frame.
+    pub snapshot: TankSnapshot,
+}
+
+/// How many received snapshots a [`RemoteTank`] keeps to interpolate between.
+const SNAPSHOT_BUFFER_LEN: usize = 8;
+
+/// A tank whose state is driven by snapshots rather than local input.
+/// The tank is shown `interpolation_delay` seconds behind its newest snapshot,
+/// so there is usually a later snapshot to interpolate toward.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct RemoteTank {
+    /// The most recently received snapshots, oldest first.
+    pub snapshots: VecDeque<TankSnapshot>,
+
+    /// The snapshot time currently shown, if any snapshot has arrived yet.
+    pub playback_time: Option<f32>,
+
+    /// How far behind the newest snapshot the tank is shown, in seconds.
+    /// Higher values hide more jitter, lower values show changes sooner.
+    pub interpolation_delay: f32,
+}
+
+impl Default for RemoteTank {
+    fn default() -> Self {
+        Self {
+            snapshots: VecDeque::new(),
+            playback_time: None,
+            interpolation_delay: 0.1,
+        }
+    }
+}
+
+impl RemoteTank {
+    /// Buffer a received snapshot. Snapshots older than the newest one arrived out of
+    /// order and are dropped.
+    pub fn receive(&mut self, snapshot: TankSnapshot) {
+        if self
+            .snapshots
+            .back()
+            .is_some_and(|newest| snapshot.time <= newest.time)
+        {
+            return;
+        }
+        self.snapshots.push_back(snapshot);
+        if self.snapshots.len() > SNAPSHOT_BUFFER_LEN {
+            self.snapshots.pop_front();
+        }
+    }
+
+    /// Advance playback by `delta` seconds and return the pose to show, if any.
+    fn advance(&mut self, delta: f32) -> Option<TankSnapshot> {
+        let (oldest, newest) = (*self.snapshots.front()?, *self.snapshots.back()?);
+        let playback_time = self
+            .playback_time
+            .map_or(newest.time - self.interpolation_delay, |time| time + delta)
+            .clamp(oldest.time, newest.time);
+        self.playback_time = Some(playback_time);
+
+        // Interpolate between the snapshots on either side of the playback time
+        let next = self
+            .snapshots
+            .iter()
+            .position(|snapshot| snapshot.time >= playback_time)
+            .unwrap_or(self.snapshots.len() - 1);
+        let to = self.snapshots[next];
+        let Some(&from) = next.checked_sub(1).and_then(|i| self.snapshots.get(i)) else {
+            return Some(to);
+        };
+        let t = (playback_time - from.time) / (to.time - from.time);
+        Some(TankSnapshot {
+            time: playback_time,
+            position: from.position.lerp(to.position, t),
+            rotation: from.rotation + shortest_angle_delta(from.rotation, to.rotation) * t,
+            turret_rotation: from.turret_rotation
+                + shortest_angle_delta(from.turret_rotation, to.turret_rotation) * t,
+        })
+    }
+}
+
+fn z_rotation(transform: &Transform) -> f32 {
+    transform.rotation.to_euler(EulerRot::XYZ).2
+}
+
+/// System to capture the current state of replicated tanks.
+fn capture_snapshots(
+    time: Res<Time>,
+    mut replicated_query: Query<(&mut Replicated, &Transform, Option<&Children>)>,
+    turret_query: Query<&Transform, (With<TurretController>, Without<Replicated>)>,
+) {
+    for (mut replicated, transform, children) in &mut replicated_query {
+        let turret = children.and_then(|children| turret_query.iter_many(children).next());
+        replicated.snapshot = capture_snapshot(time.elapsed_secs(), transform, turret);
+    }
+}
+
+/// System to show remote tanks at the pose interpolated from their snapshots.
+fn apply_snapshot(
+    time: Res<Time>,
+    mut remote_query: Query<(&mut RemoteTank, &mut Transform, Option<&Children>)>,
+    mut turret_query: Query<&mut Transform, (With<TurretController>, Without<RemoteTank>)>,
+) {
+    for (mut remote, mut transform, children) in &mut remote_query {
+        let Some(snapshot) = remote.advance(time.delta_secs()) else {
+            continue;
+        };
+
+        transform.translation = snapshot.position.extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(snapshot.rotation);
+
+        // The turret is a child of the tank.
+        let Some(children) = children else {
+            continue;
+        };
+        let mut turrets = turret_query.iter_many_mut(children);
+        while let Some(mut turret) = turrets.fetch_next() {
+            turret.rotation = Quat::from_rotation_z(snapshot.turret_rotation);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{scene::ron, time::TimeUpdateStrategy};
+
+    use super::*;
+    use crate::demo::{
+        input_command::{InputCommand, InputCommandBuffer, apply_input_commands},
+        tank_movement::TankMovementController,
+    };
+
+    #[test]
+    fn snapshot_round_trips_through_serde() {
+        let snapshot = TankSnapshot {
+            time: 12.5,
+            position: Vec2::new(-120.5, 64.25),
+            rotation: 1.5,
+            turret_rotation: -0.75,
+        };
+        let serialized = ron::to_string(&snapshot).unwrap();
+        let deserialized: TankSnapshot = ron::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, snapshot);
+    }
+
+    #[test]
+    fn capture_reads_the_tank_and_turret_rotation() {
+        let tank = Transform::from_xyz(10.0, -20.0, 3.0).with_rotation(Quat::from_rotation_z(0.5));
+        let turret = Transform::from_rotation(Quat::from_rotation_z(-0.25));
+        let snapshot = capture_snapshot(4.0, &tank, Some(&turret));
+        assert_eq!(snapshot.time, 4.0);
+        assert_eq!(snapshot.position, Vec2::new(10.0, -20.0));
+        assert!((snapshot.rotation - 0.5).abs() < 1e-5);
+        assert!((snapshot.turret_rotation + 0.25).abs() < 1e-5);
+    }
+
+    fn snapshot_at(time: f32, x: f32, rotation: f32) -> TankSnapshot {
+        TankSnapshot {
+            time,
+            position: Vec2::new(x, 0.0),
+            rotation,
+            turret_rotation: -rotation,
+        }
+    }
+
+    #[test]
+    fn remote_tanks_interpolate_between_snapshots() {
+        let mut remote = RemoteTank::default();
+        remote.receive(snapshot_at(1.0, 0.0, 0.0));
+        remote.receive(snapshot_at(1.2, 100.0, 1.0));
+        // Out-of-order snapshots are dropped
+        remote.receive(snapshot_at(1.1, -500.0, 3.0));
+
+        // Playback starts a delay behind the newest snapshot, halfway between the two
+        let pose = remote.advance(0.0).unwrap();
+        assert!((pose.position.x - 50.0).abs() < 1e-3, "{pose:?}");
+        assert!((pose.rotation - 0.5).abs() < 1e-5);
+        assert!((pose.turret_rotation + 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn remote_tank_converges_to_the_snapshot_pose() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            20,
+        )));
+        app.add_systems(Update, apply_snapshot);
+        let mut remote = RemoteTank::default();
+        remote.receive(snapshot_at(0.0, 0.0, 0.0));
+        remote.receive(snapshot_at(0.1, 80.0, 0.8));
+        let turret = app
+            .world_mut()
+            .spawn((TurretController::default(), Transform::default()))
+            .id();
+        let tank = app
+            .world_mut()
+            .spawn((remote, Transform::default()))
+            .add_child(turret)
+            .id();
+
+        // Each frame the tank moves on toward the newest snapshot, without overshooting it
+        let mut previous = 0.0;
+        for _ in 0..10 {
+            app.update();
+            let x = app.world().get::<Transform>(tank).unwrap().translation.x;
+            assert!(x >= previous && x <= 80.0, "{x}");
+            previous = x;
+        }
+        let transform = app.world().get::<Transform>(tank).unwrap();
+        assert!((transform.translation.x - 80.0).abs() < 1e-3);
+        assert!((z_rotation(transform) - 0.8).abs() < 1e-5);
+        let turret = app.world().get::<Transform>(turret).unwrap();
+        assert!((z_rotation(turret) + 0.8).abs() < 1e-5);
+    }
+
+    #[test]
+    fn remote_tanks_ignore_local_input() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(InputCommandBuffer {
+            commands: [InputCommand {
+                forward: 1.0,
+                rotation: 1.0,
+                ..default()
+            }]
+            .into(),
+            ..default()
+        });
+        app.add_systems(Update, apply_input_commands);
+        let remote = app
+            .world_mut()
+            .spawn((TankMovementController::default(), RemoteTank::default()))
+            .id();
+        app.update();
+
+        let controller = app.world().get::<TankMovementController>(remote).unwrap();
+        assert_eq!(controller.forward_intent, 0.0);
+        assert_eq!(controller.rotation_intent, 0.0);
+    }
+}
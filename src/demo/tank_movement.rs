@@ -1,21 +1,67 @@
 //! Tank-style movement system with WASD controls.
 //! A/D keys control rotation, W/S keys control forward/backward movement.
+//! With the [`ControlScheme::Arcade`] scheme, WASD steer toward world directions instead.
+
+use std::f32::consts::{FRAC_PI_4, PI, TAU};
 
 use bevy::prelude::*;
 
-use crate::{AppSystems, PausableSystems};
+use crate::{
+    AddGameplayTimers, AppSystems, PausableSystems,
+    demo::{
+        DemoConfig, ZLayers,
+        input_command::{
+            GamepadDeadzone, InputCommandBuffer, InputContextStack, KeyBindings, PlayerKeys,
+            strongest_intent,
+        },
+        player::{PlayerControls, SampledPlayerInput, TurretController},
+        snapshot::RemoteTank,
+    },
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<TankMovementController>();
     app.register_type::<ScreenWrap>();
+    app.register_type::<ScreenBounded>();
+    app.register_type::<TankVisual>();
+    app.register_type::<TurretWeightPenalty>();
+    app.register_type::<Disabled>();
+    app.register_type::<DisabledIndicator>();
+    app.register_type::<ControlScheme>();
+    app.init_resource::<ControlScheme>();
+    app.register_type::<InvertControls>();
+    app.init_resource::<InvertControls>();
+    app.register_type::<ArenaBounds>();
+    app.init_resource::<ArenaBounds>();
+    app.register_type::<CircularArena>();
+    app.register_type::<MaxFrameDelta>();
+    app.init_resource::<MaxFrameDelta>();
+
+    app.add_observer(bound_instead_of_wrapping);
+    app.add_observer(show_disabled_indicator);
+    app.add_observer(hide_disabled_indicator);
 
+    app.add_gameplay_timers(tick_disabled);
     app.add_systems(
         Update,
-        (apply_tank_movement, apply_screen_wrap)
+        (
+            apply_tank_movement,
+            apply_screen_wrap
+                .run_if(screen_wrap_enabled.and(not(resource_exists::<CircularArena>))),
+            apply_screen_bounds,
+            clamp_to_circular_arena.run_if(resource_exists::<CircularArena>),
+        )
             .chain()
             .in_set(AppSystems::Update)
             .in_set(PausableSystems),
     );
+    app.add_systems(
+        Update,
+        update_disabled_indicators
+            .after(apply_tank_movement)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
 }
 
 /// Tank movement controller that handles rotation and forward/backward movement.
@@ -27,14 +73,34 @@ pub struct TankMovementController {
     pub forward_intent: f32,
 
     /// The rotation intent (-1.0 to 1.0).
-    /// Positive values rotate clockwise, negative values rotate counter-clockwise.
+    /// Positive values rotate counter-clockwise (increasing Z rotation),
+    /// negative values rotate clockwise.
     pub rotation_intent: f32,
 
+    /// The world-space heading the tank wants to face in radians, if any.
+    /// When set, the tank rotates toward it and `rotation_intent` is ignored.
+    pub heading_intent: Option<f32>,
+
     /// Maximum forward/backward speed in world units per second.
     pub max_speed: f32,
 
+    /// The current forward/backward speed in world units per second.
+    /// Negative values move backward.
+    pub current_speed: f32,
+
+    /// How quickly the tank speeds up toward its intended speed, in world units per second squared.
+    pub acceleration: f32,
+
+    /// How quickly the tank slows down (or reverses direction), in world units per second squared.
+    pub braking: f32,
+
     /// The speed at which the tank rotates in radians per second.
     pub rotation_speed: f32,
+
+    /// The maximum rotation applied in a single frame in radians.
+    /// At very low frame rates this keeps the tank from over-rotating
+    /// (or appearing to spin the wrong way) when a frame takes a long time.
+    pub max_rotation_step: f32,
 }
 
 impl Default for TankMovementController {
@@ -42,81 +108,1236 @@ impl Default for TankMovementController {
         Self {
             forward_intent: 0.0,
             rotation_intent: 0.0,
+            heading_intent: None,
             max_speed: 400.0,
+            current_speed: 0.0,
+            acceleration: 800.0,
+            braking: 1200.0,
             rotation_speed: f32::to_radians(180.0), // 180 degrees per second
+            max_rotation_step: FRAC_PI_4,
+        }
+    }
+}
+
+/// The look of a tank model and how its art is oriented.
+/// The turret art is expected to face the same way as the chassis art.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct TankVisual {
+    /// The chassis sprite.
+    pub sprite: Handle<Image>,
+
+    /// The uniform scale of the chassis and turret sprites.
+    pub scale: f32,
+
+    /// The direction the front of the sprite faces, in radians counter-clockwise
+    /// from the local X axis. E.g. `0.0` for art facing right, `PI / 2` for art facing up.
+    pub sprite_forward_offset: f32,
+}
+
+impl TankVisual {
+    /// The direction the tank drives forward in, and its turret's barrel points in, in local space.
+    pub fn local_forward(&self) -> Vec3 {
+        Vec2::from_angle(self.sprite_forward_offset).extend(0.0)
+    }
+}
+
+/// A tank whose systems are offline, e.g. after an EMP hit.
+/// While present, the tank and its turret ignore their intents and the tank coasts to a stop.
+/// The component removes itself when the timer finishes.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Disabled(pub Timer);
+
+fn tick_disabled(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut disabled_query: Query<(Entity, &mut Disabled)>,
+) {
+    for (entity, mut disabled) in &mut disabled_query {
+        if disabled.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Disabled>();
+        }
+    }
+}
+
+/// The "systems offline" sign shown over a [`Disabled`] tank.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct DisabledIndicator;
+
+/// Where the indicator sits relative to its tank's center, in world units.
+const DISABLED_INDICATOR_OFFSET: Vec2 = Vec2::new(0.0, 48.0);
+
+/// The electric blue of the indicator.
+const DISABLED_INDICATOR_COLOR: Color = Color::srgb(0.45, 0.85, 1.0);
+
+/// How long the indicator stays on, and then off, while it flickers, in seconds.
+const DISABLED_FLICKER_SECS: f32 = 0.15;
+
+fn show_disabled_indicator(trigger: Trigger<OnAdd, Disabled>, mut commands: Commands) {
+    commands.spawn((
+        Name::new("Disabled Indicator"),
+        DisabledIndicator,
+        Text2d::new("SYSTEMS OFFLINE"),
+        TextFont::from_font_size(16.0),
+        TextColor(DISABLED_INDICATOR_COLOR),
+        ChildOf(trigger.target()),
+    ));
+}
+
+fn hide_disabled_indicator(
+    trigger: Trigger<OnRemove, Disabled>,
+    mut commands: Commands,
+    children_query: Query<&Children>,
+    indicator_query: Query<(), With<DisabledIndicator>>,
+) {
+    let Ok(children) = children_query.get(trigger.target()) else {
+        return;
+    };
+    for child in children.iter() {
+        if indicator_query.contains(child) {
+            // The tank may be despawning along with its children
+            commands.entity(child).try_despawn();
         }
     }
 }
 
+/// Keep each indicator upright above its tank, flickering like a shorted circuit.
+fn update_disabled_indicators(
+    z_layers: Res<ZLayers>,
+    tank_query: Query<(&Disabled, &Transform, &Children), Without<DisabledIndicator>>,
+    mut indicator_query: Query<(&mut Transform, &mut Visibility), With<DisabledIndicator>>,
+) {
+    for (disabled, tank_transform, children) in &tank_query {
+        let flickers = (disabled.0.elapsed_secs() / DISABLED_FLICKER_SECS) as u32;
+        let lit = flickers.is_multiple_of(2);
+        // Undo the tank's rotation and scale, so the text reads upright at its usual size
+        let inverse_rotation = tank_transform.rotation.inverse();
+        let inverse_scale = tank_transform.scale.recip();
+        let mut indicators = indicator_query.iter_many_mut(children);
+        while let Some((mut transform, mut visibility)) = indicators.fetch_next() {
+            transform.rotation = inverse_rotation;
+            transform.scale = inverse_scale;
+            transform.translation = inverse_scale
+                * (inverse_rotation * DISABLED_INDICATOR_OFFSET.extend(0.0))
+                + Vec3::Z * (z_layers.effects - z_layers.tanks);
+            *visibility = if lit {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
+
+/// How directional input is translated into tank movement.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum ControlScheme {
+    /// W/S drive forward/backward and A/D rotate the chassis.
+    #[default]
+    Tank,
+    /// WASD point toward world directions (up, left, down, right).
+    /// The tank rotates toward the pressed direction while driving forward.
+    Arcade,
+}
+
+/// Slows chassis rotation while the turret is aimed off the centerline,
+/// as if its weight were unbalancing the tank.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct TurretWeightPenalty {
+    /// The fraction of rotation speed lost with the turret at 90° to the chassis.
+    pub max_penalty: f32,
+}
+
+impl TurretWeightPenalty {
+    /// The factor chassis rotation speed is scaled by for a turret with the given local transform.
+    fn rotation_factor(&self, turret: &Transform) -> f32 {
+        // Pointing forward or backward is balanced, pointing sideways is the worst case
+        let relative_angle = turret.rotation.to_euler(EulerRot::XYZ).2;
+        1.0 - self.max_penalty.clamp(0.0, 1.0) * relative_angle.sin().abs()
+    }
+}
+
+/// Player preference for inverting controls.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct InvertControls {
+    /// Invert chassis rotation input.
+    /// Arcade steering points toward world directions, so it isn't affected.
+    pub steering: bool,
+
+    /// Invert forward/backward input, so the forward key reverses.
+    /// Arcade steering drives toward the pressed direction, so it isn't affected.
+    pub throttle: bool,
+
+    /// Invert turret rotation input.
+    pub turret: bool,
+}
+
 /// Screen wrap component to keep entities within screen bounds.
+/// Don't combine with [`ScreenBounded`].
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct ScreenWrap;
 
-fn apply_tank_movement(
+/// Stops an entity at the window edges instead of wrapping it around.
+/// An entity should never have both this and [`ScreenWrap`]; if it does, wrapping wins.
+/// When [`DemoConfig::screen_wrap`] is off, entities given [`ScreenWrap`] get this instead.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct ScreenBounded {
+    /// How far from the window edges the entity's center is kept, in world units,
+    /// so the sprite stays fully on screen.
+    pub margin: f32,
+}
+
+impl Default for ScreenBounded {
+    fn default() -> Self {
+        Self { margin: 40.0 }
+    }
+}
+
+/// Keep entities that would wrap around on screen instead while wrapping is disabled.
+fn bound_instead_of_wrapping(
+    trigger: Trigger<OnAdd, ScreenWrap>,
+    config: Option<Res<DemoConfig>>,
+    mut commands: Commands,
+) {
+    if config.is_some_and(|config| !config.screen_wrap) {
+        commands
+            .entity(trigger.target())
+            .remove::<ScreenWrap>()
+            .insert(ScreenBounded::default());
+    }
+}
+
+/// System to move tanks according to their [`TankMovementController`].
+/// Remote tanks are moved by their snapshots instead.
+pub fn apply_tank_movement(
     time: Res<Time>,
-    mut movement_query: Query<(&TankMovementController, &mut Transform)>,
+    max_delta: Res<MaxFrameDelta>,
+    mut movement_query: Query<
+        (
+            &mut TankMovementController,
+            &mut Transform,
+            Option<&TankVisual>,
+            Option<(&TurretWeightPenalty, &Children)>,
+            Has<Disabled>,
+        ),
+        Without<RemoteTank>,
+    >,
+    turret_query: Query<&Transform, (With<TurretController>, Without<TankMovementController>)>,
 ) {
-    for (controller, mut transform) in &mut movement_query {
-        // Apply rotation based on rotation intent
-        let rotation_delta =
-            controller.rotation_intent * controller.rotation_speed * time.delta_secs();
+    let dt = max_delta.delta_secs(&time);
+    if dt == 0.0 {
+        return;
+    }
+
+    for (mut controller, mut transform, visual, weight_penalty, disabled) in &mut movement_query {
+        // Get the tank's forward direction (X axis in local space unless the art says otherwise)
+        let local_forward = visual.map_or(Vec3::X, TankVisual::local_forward);
+        let forward_direction = transform.rotation * local_forward;
+
+        // A heavy turret aimed off-center slows down chassis rotation
+        let rotation_factor = weight_penalty.map_or(1.0, |(penalty, children)| {
+            turret_query
+                .iter_many(children)
+                .next()
+                .map_or(1.0, |turret| penalty.rotation_factor(turret))
+        });
+
+        // Apply rotation based on heading intent if there is one, rotation intent otherwise
+        let max_rotation_delta =
+            (controller.rotation_speed * rotation_factor * dt).min(controller.max_rotation_step);
+        // A disabled tank ignores its intents and coasts to a stop
+        let rotation_delta = match controller.heading_intent {
+            _ if disabled => 0.0,
+            Some(heading) => {
+                let current_heading = forward_direction.xy().to_angle();
+                let difference = shortest_angle_delta(current_heading, heading);
+                difference.clamp(-max_rotation_delta, max_rotation_delta)
+            }
+            None => controller.rotation_intent.clamp(-1.0, 1.0) * max_rotation_delta,
+        };
         transform.rotate_z(rotation_delta);
 
+        // Ramp the speed toward the intended speed, so the tank neither jumps to full speed
+        // nor stops dead. Without intent it coasts to a stop at the braking rate.
+        let target_speed = if disabled {
+            0.0
+        } else {
+            controller.forward_intent.clamp(-1.0, 1.0) * controller.max_speed
+        };
+        let current_speed = controller.current_speed;
+        let speeding_up = target_speed.abs() > current_speed.abs()
+            && (current_speed == 0.0 || target_speed.signum() == current_speed.signum());
+        let rate = if speeding_up {
+            controller.acceleration
+        } else {
+            controller.braking
+        };
+        let max_speed_delta = rate * dt;
+        controller.current_speed = (current_speed
+            + (target_speed - current_speed).clamp(-max_speed_delta, max_speed_delta))
+        .clamp(-controller.max_speed, controller.max_speed);
+
         // Apply forward/backward movement based on current rotation
-        if controller.forward_intent != 0.0 {
-            // Get the tank's forward direction (X axis in local space since sprite is rotated 90 degrees)
-            let forward_direction = transform.rotation * Vec3::X;
-            let movement_distance =
-                controller.forward_intent * controller.max_speed * time.delta_secs();
+        if controller.current_speed != 0.0 {
+            let forward_direction = transform.rotation * local_forward;
+            let movement_distance = controller.current_speed * dt;
             let translation_delta = forward_direction * movement_distance;
             transform.translation += translation_delta;
         }
     }
 }
 
+/// The signed rotation in radians that turns `current` into `target` the short way around.
+/// The result is in [-PI, PI), so aiming across the ±PI seam doesn't spin all the way around.
+pub fn shortest_angle_delta(current: f32, target: f32) -> f32 {
+    (target - current + PI).rem_euclid(TAU) - PI
+}
+
+/// The longest frame, in seconds, that gameplay movement will simulate.
+/// A hitch (e.g. after a breakpoint) is clamped to this so tanks don't teleport across the map.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct MaxFrameDelta {
+    /// The maximum delta in seconds.
+    pub max_delta: f32,
+}
+
+impl Default for MaxFrameDelta {
+    fn default() -> Self {
+        Self { max_delta: 0.1 }
+    }
+}
+
+impl MaxFrameDelta {
+    /// This frame's delta in seconds, clamped to [`Self::max_delta`].
+    pub fn delta_secs(&self, time: &Time) -> f32 {
+        time.delta_secs().min(self.max_delta)
+    }
+}
+
+fn screen_wrap_enabled(config: Res<DemoConfig>) -> bool {
+    config.screen_wrap
+}
+
+/// The size of the play area, centered on the origin.
+/// Used for screen wrap when there isn't exactly one primary window,
+/// e.g. in headless apps or multi-window setups.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct ArenaBounds {
+    /// The width and height of the play area in world units.
+    pub size: Vec2,
+}
+
+impl Default for ArenaBounds {
+    fn default() -> Self {
+        // The default window size.
+        Self {
+            size: Vec2::new(1280.0, 720.0),
+        }
+    }
+}
+
+/// A round play area. While this resource exists, tanks are kept inside the circle
+/// (sliding along its edge) and rectangular screen wrap is turned off.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct CircularArena {
+    /// The center of the arena in world units.
+    pub center: Vec2,
+
+    /// The radius of the arena in world units.
+    pub radius: f32,
+}
+
+fn clamp_to_circular_arena(
+    arena: Res<CircularArena>,
+    mut tank_query: Query<&mut Transform, With<TankMovementController>>,
+) {
+    for mut transform in &mut tank_query {
+        let offset = transform.translation.xy() - arena.center;
+        if offset.length_squared() <= arena.radius * arena.radius {
+            continue;
+        }
+
+        // Pull back onto the edge; the tangential part of the movement is kept, so tanks slide
+        let clamped = arena.center + offset.normalize_or_zero() * arena.radius;
+        transform.translation.x = clamped.x;
+        transform.translation.y = clamped.y;
+    }
+}
+
+/// The size of the area entities wrap around in: the window (or [`ArenaBounds`] without one)
+/// plus a margin, so sprites are fully off screen before they wrap.
+pub fn wrap_area_size(window_size: Option<Vec2>, arena: &ArenaBounds) -> Vec2 {
+    window_size.unwrap_or(arena.size) + 256.0
+}
+
+/// The shortest way from one point to another `offset` away in a wrap area of `size`,
+/// which may cross an edge.
+pub fn wrapped_offset(offset: Vec2, size: Vec2) -> Vec2 {
+    let half_size = size / 2.0;
+    (offset + half_size).rem_euclid(size) - half_size
+}
+
 fn apply_screen_wrap(
     window: Query<&Window, With<bevy::window::PrimaryWindow>>,
+    arena: Res<ArenaBounds>,
+    mut warned: Local<bool>,
     mut wrap_query: Query<&mut Transform, With<ScreenWrap>>,
 ) {
-    if let Ok(window) = window.single() {
-        let size = window.size() + 256.0;
-        let half_size = size / 2.0;
-        for mut transform in &mut wrap_query {
-            let position = transform.translation.xy();
-            let wrapped = (position + half_size).rem_euclid(size) - half_size;
-            transform.translation = wrapped.extend(transform.translation.z);
+    let window_size = match window.single() {
+        Ok(window) => Some(window.size()),
+        Err(error) => {
+            if !*warned {
+                warn!("Screen wrap is using `ArenaBounds` instead of the window: {error}");
+                *warned = true;
+            }
+            None
         }
+    };
+    let size = wrap_area_size(window_size, &arena);
+    let half_size = size / 2.0;
+    for mut transform in &mut wrap_query {
+        let position = transform.translation.xy();
+        let wrapped = (position + half_size).rem_euclid(size) - half_size;
+        transform.translation = wrapped.extend(transform.translation.z);
     }
 }
 
-/// System to record tank input from keyboard.
+fn apply_screen_bounds(
+    window: Query<&Window, With<bevy::window::PrimaryWindow>>,
+    arena: Res<ArenaBounds>,
+    mut bounded_query: Query<
+        (
+            &ScreenBounded,
+            &mut Transform,
+            Option<&mut TankMovementController>,
+            Option<&TankVisual>,
+        ),
+        Without<ScreenWrap>,
+    >,
+) {
+    let size = window.single().map_or(arena.size, Window::size);
+    for (bounded, mut transform, controller, visual) in &mut bounded_query {
+        let half_extents = (size / 2.0 - bounded.margin).max(Vec2::ZERO);
+        let position = transform.translation.xy();
+        let clamped = position.clamp(-half_extents, half_extents);
+        if clamped == position {
+            continue;
+        }
+        transform.translation.x = clamped.x;
+        transform.translation.y = clamped.y;
+
+        // Drop the part of the tank's speed that drives into the edge, so it doesn't keep
+        // grinding against it but can still slide along it
+        let Some(mut controller) = controller else {
+            continue;
+        };
+        let local_forward = visual.map_or(Vec3::X, TankVisual::local_forward);
+        let forward = (transform.rotation * local_forward).xy();
+        let mut velocity = forward * controller.current_speed;
+        let pushed_back = position - clamped;
+        if pushed_back.x * velocity.x > 0.0 {
+            velocity.x = 0.0;
+        }
+        if pushed_back.y * velocity.y > 0.0 {
+            velocity.y = 0.0;
+        }
+        controller.current_speed = velocity.dot(forward);
+    }
+}
+
+/// System to record tank input from keyboard and gamepad into [`InputCommandBuffer::sampled`],
+/// and each [`PlayerControls`] tank's own keys and gamepad into its [`SampledPlayerInput`].
 /// This should be called from the player module.
 pub fn record_tank_input(
     input: Res<ButtonInput<KeyCode>>,
-    mut controller_query: Query<&mut TankMovementController>,
+    bindings: Res<KeyBindings>,
+    gamepads: Query<&Gamepad>,
+    deadzone: Res<GamepadDeadzone>,
+    control_scheme: Res<ControlScheme>,
+    invert: Res<InvertControls>,
+    contexts: Res<InputContextStack>,
+    mut buffer: ResMut<InputCommandBuffer>,
+    mut player_query: Query<(&PlayerControls, &mut SampledPlayerInput)>,
 ) {
-    // Collect forward/backward input (W/S keys)
+    let gameplay = contexts.is_gameplay();
+    let drive = |keys: &PlayerKeys, left_stick: Vec2| {
+        if gameplay {
+            drive_input(&input, keys, left_stick, *control_scheme, *invert)
+        } else {
+            (0.0, 0.0, None)
+        }
+    };
+
+    let command = &mut buffer.sampled;
+    let left_stick = deadzone.stick(&gamepads, Gamepad::left_stick);
+    (command.forward, command.rotation, command.heading) =
+        drive(&bindings.single_player, left_stick);
+
+    // Each local player drives with their own keys and gamepad
+    for (controls, mut sampled) in &mut player_query {
+        let left_stick = controls
+            .find_gamepad(&gamepads)
+            .map_or(Vec2::ZERO, |gamepad| {
+                deadzone.gamepad_stick(gamepad, Gamepad::left_stick)
+            });
+        let sampled = &mut sampled.0;
+        (sampled.forward, sampled.rotation, sampled.heading) =
+            drive(bindings.keys(controls.bindings), left_stick);
+    }
+}
+
+/// The forward, rotation and heading intents from `bindings` and a gamepad's `left_stick`.
+fn drive_input(
+    input: &ButtonInput<KeyCode>,
+    keys: &PlayerKeys,
+    left_stick: Vec2,
+    control_scheme: ControlScheme,
+    invert: InvertControls,
+) -> (f32, f32, Option<f32>) {
+    if control_scheme == ControlScheme::Arcade {
+        return arcade_input(input, keys, left_stick);
+    }
+
+    // Collect forward/backward input (W/S keys by default)
     let mut forward_intent = 0.0;
-    if input.pressed(KeyCode::KeyW) {
+    if input.pressed(keys.forward) {
         forward_intent += 1.0;
     }
-    if input.pressed(KeyCode::KeyS) {
+    if input.pressed(keys.backward) {
         forward_intent -= 1.0;
     }
 
-    // Collect rotation input (A/D keys)
+    // Collect rotation input (A/D keys by default)
     let mut rotation_intent = 0.0;
-    if input.pressed(KeyCode::KeyA) {
+    if input.pressed(keys.rotate_left) {
         rotation_intent += 1.0; // Counter-clockwise
     }
-    if input.pressed(KeyCode::KeyD) {
+    if input.pressed(keys.rotate_right) {
         rotation_intent -= 1.0; // Clockwise
     }
 
-    // Apply input to all tank movement controllers
-    for mut controller in &mut controller_query {
-        controller.forward_intent = forward_intent;
-        controller.rotation_intent = rotation_intent;
+    // Left stick Y drives, left stick X rotates (pushing right turns clockwise)
+    let mut forward_intent = strongest_intent(forward_intent, left_stick.y).clamp(-1.0, 1.0);
+    if invert.throttle {
+        forward_intent = -forward_intent;
+    }
+    let mut rotation_intent = strongest_intent(rotation_intent, -left_stick.x).clamp(-1.0, 1.0);
+    if invert.steering {
+        rotation_intent = -rotation_intent;
+    }
+
+    (forward_intent, rotation_intent, None)
+}
+
+/// Arcade-style input, where WASD and the left stick point toward world directions.
+fn arcade_input(
+    input: &ButtonInput<KeyCode>,
+    keys: &PlayerKeys,
+    left_stick: Vec2,
+) -> (f32, f32, Option<f32>) {
+    // Collect directional input (WASD keys by default)
+    let mut direction = Vec2::ZERO;
+    if input.pressed(keys.forward) {
+        direction.y += 1.0;
+    }
+    if input.pressed(keys.backward) {
+        direction.y -= 1.0;
+    }
+    if input.pressed(keys.rotate_left) {
+        direction.x -= 1.0;
+    }
+    if input.pressed(keys.rotate_right) {
+        direction.x += 1.0;
+    }
+
+    // Drive toward the pressed direction, or stop if there is none.
+    // The keys always drive at full speed, so the stick only counts without them.
+    if direction != Vec2::ZERO {
+        (1.0, 0.0, Some(direction.to_angle()))
+    } else if left_stick != Vec2::ZERO {
+        (
+            left_stick.length().min(1.0),
+            0.0,
+            Some(left_stick.to_angle()),
+        )
+    } else {
+        (0.0, 0.0, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f32::consts::FRAC_PI_2, time::Duration};
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use crate::demo::{
+        input_command::{InputCommand, apply_input_commands, queue_input_command},
+        player::{TankInput, apply_tank_input},
+    };
+
+    use super::*;
+
+    const EPSILON: f32 = 1e-5;
+
+    /// The fixed frame time of test apps, in seconds.
+    const DT: f32 = 0.1;
+
+    /// An app that steps [`apply_tank_movement`] by [`DT`] each update.
+    fn movement_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            DT,
+        )));
+        app.init_resource::<MaxFrameDelta>();
+        app.add_systems(Update, apply_tank_movement);
+        // The first update only starts the clock
+        app.update();
+        app
+    }
+
+    fn controller(app: &App, tank: Entity) -> &TankMovementController {
+        app.world().get::<TankMovementController>(tank).unwrap()
+    }
+
+    #[test]
+    fn speed_ramps_up_with_acceleration_and_down_with_braking() {
+        let mut app = movement_app();
+        let tank = app
+            .world_mut()
+            .spawn((
+                TankMovementController {
+                    forward_intent: 1.0,
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+
+        app.update();
+        let speed = controller(&app, tank).current_speed;
+        assert!((speed - 800.0 * DT).abs() < EPSILON, "{speed}");
+
+        // Acceleration stops at max speed
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(controller(&app, tank).current_speed, 400.0);
+
+        // Without intent the tank brakes rather than stopping dead
+        app.world_mut()
+            .get_mut::<TankMovementController>(tank)
+            .unwrap()
+            .forward_intent = 0.0;
+        app.update();
+        let speed = controller(&app, tank).current_speed;
+        assert!((speed - (400.0 - 1200.0 * DT)).abs() < EPSILON, "{speed}");
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(controller(&app, tank).current_speed, 0.0);
+    }
+
+    #[test]
+    fn tank_drives_toward_its_art_forward() {
+        let mut app = movement_app();
+        // Art facing up, so the unrotated tank faces up too
+        let tank = app
+            .world_mut()
+            .spawn((
+                TankMovementController {
+                    forward_intent: 1.0,
+                    ..default()
+                },
+                TankVisual {
+                    sprite: Handle::default(),
+                    scale: 1.0,
+                    sprite_forward_offset: FRAC_PI_2,
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.update();
+        app.update();
+
+        let position = app.world().get::<Transform>(tank).unwrap().translation;
+        assert!(position.y > 0.0, "{position}");
+        assert!(position.x.abs() < EPSILON, "{position}");
+    }
+
+    /// How far a tank with `max_penalty` turns in one frame, with its turret at `turret_angle`.
+    fn turn_with_turret_at(turret_angle: f32, max_penalty: f32) -> f32 {
+        let mut app = movement_app();
+        let tank = app
+            .world_mut()
+            .spawn((
+                TankMovementController {
+                    rotation_intent: 1.0,
+                    ..default()
+                },
+                TurretWeightPenalty { max_penalty },
+                Transform::default(),
+            ))
+            .id();
+        app.world_mut().spawn((
+            TurretController::default(),
+            Transform::from_rotation(Quat::from_rotation_z(turret_angle)),
+            ChildOf(tank),
+        ));
+        app.update();
+        app.world()
+            .get::<Transform>(tank)
+            .unwrap()
+            .rotation
+            .to_euler(EulerRot::XYZ)
+            .2
+    }
+
+    #[test]
+    fn sideways_turret_slows_rotation_by_the_full_penalty() {
+        let full_turn = TankMovementController::default().rotation_speed * DT;
+        let forward = turn_with_turret_at(0.0, 0.5);
+        assert!((forward - full_turn).abs() < EPSILON, "{forward}");
+        let sideways = turn_with_turret_at(FRAC_PI_2, 0.5);
+        assert!((sideways - 0.5 * full_turn).abs() < EPSILON, "{sideways}");
+    }
+
+    #[test]
+    fn circular_arena_slides_tanks_along_its_edge() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            DT,
+        )));
+        app.init_resource::<MaxFrameDelta>();
+        app.insert_resource(CircularArena {
+            center: Vec2::ZERO,
+            radius: 100.0,
+        });
+        app.add_systems(
+            Update,
+            (apply_tank_movement, clamp_to_circular_arena).chain(),
+        );
+        // On the edge, driving outward at 45° to it
+        let tank = app
+            .world_mut()
+            .spawn((
+                TankMovementController {
+                    forward_intent: 1.0,
+                    current_speed: 400.0,
+                    ..default()
+                },
+                Transform::from_xyz(100.0, 0.0, 0.0)
+                    .with_rotation(Quat::from_rotation_z(FRAC_PI_4)),
+            ))
+            .id();
+        app.update();
+
+        let position = |app: &App| app.world().get::<Transform>(tank).unwrap().translation.xy();
+        let mut previous = position(&app);
+        for _ in 0..5 {
+            app.update();
+            let current = position(&app);
+            // Held on the edge, neither pushed inward nor stopped dead
+            assert!((current.length() - 100.0).abs() < 1e-3, "{current}");
+            // Sliding counter-clockwise along the tangent
+            assert!(current.to_angle() > previous.to_angle(), "{current}");
+            previous = current;
+        }
+    }
+
+    #[test]
+    fn long_frames_are_clamped_to_max_frame_delta() {
+        let mut app = movement_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(2)));
+        let tank = app
+            .world_mut()
+            .spawn((
+                TankMovementController {
+                    forward_intent: 1.0,
+                    current_speed: 400.0,
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.update();
+
+        // 2 seconds at full speed would be 800 units, but only 0.1 seconds are simulated
+        let max_delta = MaxFrameDelta::default().max_delta;
+        let position = app.world().get::<Transform>(tank).unwrap().translation;
+        assert!(
+            (position.x - 400.0 * max_delta).abs() < EPSILON,
+            "{position}"
+        );
+    }
+
+    #[test]
+    fn reversing_brakes_before_accelerating_backward() {
+        let mut app = movement_app();
+        let tank = app
+            .world_mut()
+            .spawn((
+                TankMovementController {
+                    forward_intent: -1.0,
+                    current_speed: 400.0,
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.update();
+        let speed = controller(&app, tank).current_speed;
+        assert!((speed - (400.0 - 1200.0 * DT)).abs() < EPSILON, "{speed}");
+    }
+
+    #[test]
+    fn disabled_tank_coasts_to_a_stop() {
+        let mut app = movement_app();
+        let tank = app
+            .world_mut()
+            .spawn((
+                TankMovementController {
+                    forward_intent: 1.0,
+                    rotation_intent: 1.0,
+                    current_speed: 400.0,
+                    ..default()
+                },
+                Transform::default(),
+                Disabled(Timer::from_seconds(10.0, TimerMode::Once)),
+            ))
+            .id();
+
+        app.update();
+        let speed = controller(&app, tank).current_speed;
+        assert!((speed - (400.0 - 1200.0 * DT)).abs() < EPSILON, "{speed}");
+        let transform = app.world().get::<Transform>(tank).unwrap();
+        assert_eq!(transform.rotation, Quat::IDENTITY);
+        assert!(transform.translation.x > 0.0);
+
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(controller(&app, tank).current_speed, 0.0);
+    }
+
+    #[test]
+    fn disabled_tank_ignores_input_until_it_recovers() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            DT,
+        )));
+        app.init_resource::<MaxFrameDelta>();
+        app.init_resource::<ZLayers>();
+        app.add_observer(show_disabled_indicator);
+        app.add_observer(hide_disabled_indicator);
+        app.add_systems(
+            Update,
+            (
+                tick_disabled,
+                apply_tank_input,
+                apply_tank_movement,
+                update_disabled_indicators,
+            )
+                .chain(),
+        );
+        let tank = app
+            .world_mut()
+            .spawn((
+                TankInput {
+                    forward: 1.0,
+                    rotation: 1.0,
+                    ..default()
+                },
+                TankMovementController::default(),
+                Transform::default(),
+                Disabled(Timer::from_seconds(0.5, TimerMode::Once)),
+            ))
+            .id();
+        let indicators = |app: &mut App| {
+            app.world_mut()
+                .query_filtered::<&ChildOf, With<DisabledIndicator>>()
+                .iter(app.world())
+                .filter(|child_of| child_of.parent() == tank)
+                .count()
+        };
+        assert_eq!(indicators(&mut app), 1);
+
+        // The first update only starts the clock, then the timer runs for 4 more updates
+        for _ in 0..5 {
+            app.update();
+            let transform = app.world().get::<Transform>(tank).unwrap();
+            assert_eq!(*transform, Transform::default());
+        }
+        assert_eq!(indicators(&mut app), 1);
+
+        // The timer finishes on the next update, and the tank drives again right after
+        app.update();
+        assert!(app.world().get::<Disabled>(tank).is_none());
+        assert_eq!(indicators(&mut app), 0);
+        app.update();
+        let transform = app.world().get::<Transform>(tank).unwrap();
+        assert!(transform.translation.x > 0.0);
+        assert!(transform.rotation.to_euler(EulerRot::ZYX).0 > 0.0);
+    }
+
+    #[test]
+    fn shortest_angle_delta_turns_the_short_way() {
+        assert!((shortest_angle_delta(0.0, FRAC_PI_2) - FRAC_PI_2).abs() < EPSILON);
+        assert!((shortest_angle_delta(FRAC_PI_2, 0.0) + FRAC_PI_2).abs() < EPSILON);
+        assert!(shortest_angle_delta(1.0, 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn shortest_angle_delta_crosses_the_pi_seam() {
+        // Just below +PI to just above -PI is a small counter-clockwise step
+        let delta = shortest_angle_delta(PI - 0.1, -PI + 0.1);
+        assert!((delta - 0.2).abs() < EPSILON);
+
+        // And back again is a small clockwise step
+        let delta = shortest_angle_delta(-PI + 0.1, PI - 0.1);
+        assert!((delta + 0.2).abs() < EPSILON);
+    }
+
+    #[test]
+    fn shortest_angle_delta_ignores_full_turns() {
+        let delta = shortest_angle_delta(0.0, TAU + 0.5);
+        assert!((delta - 0.5).abs() < EPSILON);
+        let delta = shortest_angle_delta(3.0 * TAU, -0.5);
+        assert!((delta + 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn shortest_angle_delta_stays_in_range() {
+        for step in 0..64 {
+            let target = step as f32 * 0.37 - 12.0;
+            let delta = shortest_angle_delta(0.25, target);
+            assert!((-PI..PI).contains(&delta), "{delta} out of range");
+        }
+    }
+
+    /// An app that records tank input with the given settings.
+    fn record_app(control_scheme: ControlScheme, invert: InvertControls) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<KeyBindings>();
+        app.init_resource::<GamepadDeadzone>();
+        app.insert_resource(control_scheme);
+        app.insert_resource(invert);
+        app.init_resource::<InputContextStack>();
+        app.init_resource::<InputCommandBuffer>();
+        app.add_systems(Update, record_tank_input);
+        app
+    }
+
+    /// The command sampled while holding `keys`.
+    fn sample(app: &mut App, keys: &[KeyCode]) -> InputCommand {
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.release_all();
+        for &key in keys {
+            input.press(key);
+        }
+        app.update();
+        app.world().resource::<InputCommandBuffer>().sampled
+    }
+
+    #[test]
+    fn holding_a_turns_the_tank_counter_clockwise() {
+        let mut app = record_app(ControlScheme::Tank, InvertControls::default());
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            DT,
+        )));
+        app.init_resource::<MaxFrameDelta>();
+        app.add_systems(
+            Update,
+            (
+                queue_input_command,
+                apply_input_commands,
+                apply_tank_movement,
+            )
+                .chain()
+                .after(record_tank_input),
+        );
+        let tank = app
+            .world_mut()
+            .spawn((TankMovementController::default(), Transform::default()))
+            .id();
+
+        // The first update only starts the clock
+        sample(&mut app, &[KeyCode::KeyA]);
+        sample(&mut app, &[KeyCode::KeyA]);
+        let transform = app.world().get::<Transform>(tank).unwrap();
+        let (z_rotation, _, _) = transform.rotation.to_euler(EulerRot::ZYX);
+        let expected = controller(&app, tank).rotation_speed * DT;
+        assert!((z_rotation - expected).abs() < EPSILON, "{z_rotation}");
+        // Counter-clockwise turns the forward direction from +X toward +Y
+        assert!((transform.rotation * Vec3::X).y > 0.0);
+    }
+
+    #[test]
+    fn inverted_steering_swaps_left_and_right() {
+        let mut normal = record_app(ControlScheme::Tank, InvertControls::default());
+        let mut inverted = record_app(
+            ControlScheme::Tank,
+            InvertControls {
+                steering: true,
+                ..default()
+            },
+        );
+        let right = sample(&mut normal, &[KeyCode::KeyD]).rotation;
+        assert_eq!(right, -1.0);
+        assert_eq!(sample(&mut inverted, &[KeyCode::KeyA]).rotation, right);
+        // Driving isn't affected
+        assert_eq!(sample(&mut inverted, &[KeyCode::KeyW]).forward, 1.0);
+    }
+
+    #[test]
+    fn inverted_throttle_swaps_forward_and_backward() {
+        let mut inverted = record_app(
+            ControlScheme::Tank,
+            InvertControls {
+                throttle: true,
+                ..default()
+            },
+        );
+        assert_eq!(sample(&mut inverted, &[KeyCode::KeyW]).forward, -1.0);
+        assert_eq!(sample(&mut inverted, &[KeyCode::KeyS]).forward, 1.0);
+        // Steering isn't affected
+        assert_eq!(sample(&mut inverted, &[KeyCode::KeyA]).rotation, 1.0);
+    }
+
+    #[test]
+    fn arcade_keys_set_a_heading_intent() {
+        let mut app = record_app(ControlScheme::Arcade, InvertControls::default());
+
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.press(KeyCode::KeyW);
+        input.press(KeyCode::KeyA);
+        app.update();
+
+        let command = app.world().resource::<InputCommandBuffer>().sampled;
+        assert_eq!(command.forward, 1.0);
+        assert_eq!(command.rotation, 0.0);
+        let heading = command.heading.unwrap();
+        assert!((heading - 3.0 * FRAC_PI_4).abs() < EPSILON, "{heading}");
+
+        // Releasing the keys clears the heading
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.release_all();
+        app.update();
+        let command = app.world().resource::<InputCommandBuffer>().sampled;
+        assert_eq!(command.forward, 0.0);
+        assert_eq!(command.heading, None);
+    }
+
+    #[test]
+    fn heading_intent_turns_toward_the_heading_without_overshooting() {
+        let mut app = movement_app();
+        let tank = app
+            .world_mut()
+            .spawn((
+                TankMovementController {
+                    heading_intent: Some(FRAC_PI_2),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+
+        // 180 degrees per second turns 18 degrees per frame
+        app.update();
+        let angle = app
+            .world()
+            .get::<Transform>(tank)
+            .unwrap()
+            .rotation
+            .to_euler(EulerRot::XYZ)
+            .2;
+        assert!(
+            (angle - 180f32.to_radians() * DT).abs() < EPSILON,
+            "{angle}"
+        );
+
+        for _ in 0..10 {
+            app.update();
+        }
+        let angle = app
+            .world()
+            .get::<Transform>(tank)
+            .unwrap()
+            .rotation
+            .to_euler(EulerRot::XYZ)
+            .2;
+        assert!((angle - FRAC_PI_2).abs() < EPSILON, "{angle}");
+    }
+
+    #[test]
+    fn rotation_per_frame_is_limited_to_max_rotation_step() {
+        let mut app = movement_app();
+        let tank = app
+            .world_mut()
+            .spawn((
+                TankMovementController {
+                    rotation_intent: 1.0,
+                    // Would turn 100 radians in a frame without the limit
+                    rotation_speed: 100.0 / DT,
+                    max_rotation_step: 0.2,
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+
+        app.update();
+        let angle = app
+            .world()
+            .get::<Transform>(tank)
+            .unwrap()
+            .rotation
+            .to_euler(EulerRot::XYZ)
+            .2;
+        assert!((angle - 0.2).abs() < EPSILON, "{angle}");
+    }
+
+    /// An app that keeps [`ScreenBounded`] entities inside a 200x100 arena.
+    #[test]
+    fn windowless_app_wraps_at_the_arena_bounds() {
+        let mut app = movement_app();
+        assert!(
+            app.world_mut()
+                .query::<&Window>()
+                .iter(app.world())
+                .next()
+                .is_none()
+        );
+        app.insert_resource(ArenaBounds {
+            size: Vec2::new(200.0, 100.0),
+        });
+        app.add_systems(Update, apply_screen_wrap.after(apply_tank_movement));
+        let tank = app
+            .world_mut()
+            .spawn((
+                ScreenWrap,
+                TankMovementController {
+                    forward_intent: 1.0,
+                    current_speed: 400.0,
+                    ..default()
+                },
+                Transform::from_xyz(210.0, 0.0, 0.0),
+            ))
+            .id();
+
+        // The wrap area is the arena plus a margin, 456 units wide, so its edge is at x = 228.
+        // The tank drives 40 units past 210 and comes back in on the far side.
+        app.update();
+        let x = app.world().get::<Transform>(tank).unwrap().translation.x;
+        assert!((x - (250.0 - 456.0)).abs() < EPSILON, "{x}");
+    }
+
+    fn bounds_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(ArenaBounds {
+            size: Vec2::new(200.0, 100.0),
+        });
+        app.add_systems(Update, apply_screen_bounds);
+        app
+    }
+
+    #[test]
+    fn head_on_contact_stops_the_tank() {
+        let mut app = bounds_app();
+        let tank = app
+            .world_mut()
+            .spawn((
+                ScreenBounded { margin: 0.0 },
+                Transform::from_xyz(120.0, 0.0, 0.0),
+                TankMovementController {
+                    current_speed: 300.0,
+                    ..default()
+                },
+            ))
+            .id();
+        app.update();
+
+        assert_eq!(
+            app.world().get::<Transform>(tank).unwrap().translation.x,
+            100.0
+        );
+        assert!(controller(&app, tank).current_speed.abs() < EPSILON);
+    }
+
+    #[test]
+    fn glancing_contact_keeps_the_speed_along_the_edge() {
+        let mut app = bounds_app();
+        // Driving mostly up, slightly into the right edge
+        let angle = f32::to_radians(80.0);
+        let tank = app
+            .world_mut()
+            .spawn((
+                ScreenBounded { margin: 0.0 },
+                Transform::from_xyz(101.0, 0.0, 0.0).with_rotation(Quat::from_rotation_z(angle)),
+                TankMovementController {
+                    current_speed: 300.0,
+                    ..default()
+                },
+            ))
+            .id();
+        app.update();
+
+        let speed = controller(&app, tank).current_speed;
+        let expected = 300.0 * angle.sin() * angle.sin();
+        assert!((speed - expected).abs() < 1e-3, "{speed}");
+    }
+
+    #[test]
+    fn driving_away_from_the_edge_keeps_full_speed() {
+        let mut app = bounds_app();
+        let tank = app
+            .world_mut()
+            .spawn((
+                ScreenBounded { margin: 0.0 },
+                Transform::from_xyz(120.0, 0.0, 0.0),
+                TankMovementController {
+                    current_speed: -300.0,
+                    ..default()
+                },
+            ))
+            .id();
+        app.update();
+        assert_eq!(controller(&app, tank).current_speed, -300.0);
+    }
+
+    #[test]
+    fn wrapping_falls_back_to_bounds_when_disabled() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(DemoConfig {
+            screen_wrap: false,
+            audio: false,
+            two_players: false,
+        });
+        app.add_observer(bound_instead_of_wrapping);
+        app.add_observer(show_disabled_indicator);
+        app.add_observer(hide_disabled_indicator);
+        let tank = app.world_mut().spawn(ScreenWrap).id();
+        app.update();
+
+        let tank = app.world().entity(tank);
+        assert!(!tank.contains::<ScreenWrap>());
+        assert!(tank.contains::<ScreenBounded>());
     }
 }
@@ -0,0 +1,233 @@
+//! Per-frame gameplay telemetry for balancing, exported as CSV.
+//! Samples are buffered in memory and only written out on exit or when pressing [`FLUSH_KEY`].
+
+use std::{fmt::Write as _, path::PathBuf};
+
+use bevy::{ecs::entity::EntityHashMap, input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        player::{Player, ShotsFired},
+        tank_movement::{TankMovementController, shortest_angle_delta},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Telemetry>();
+
+    app.add_systems(
+        Update,
+        record_telemetry
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+    app.add_systems(
+        Update,
+        flush_telemetry.run_if(input_just_pressed(FLUSH_KEY)),
+    );
+    // Windows are closed in `PostUpdate`, so catch the exit afterwards
+    app.add_systems(Last, flush_telemetry.run_if(on_event::<AppExit>));
+}
+
+const FLUSH_KEY: KeyCode = KeyCode::F9;
+
+/// A single frame of telemetry for one player tank.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TelemetrySample {
+    /// Seconds since startup.
+    pub time: f32,
+
+    /// The player tank the sample is for, so local multiplayer logs one row per tank.
+    pub tank: Entity,
+
+    /// The player's speed in world units per second.
+    /// Negative values are reversing.
+    pub speed: f32,
+
+    /// The player's rotation rate in radians per second.
+    /// Positive values are counter-clockwise.
+    pub rotation_rate: f32,
+
+    /// How many shots the player has fired so far.
+    pub shots: u32,
+}
+
+/// Buffered telemetry samples and where to write them.
+#[derive(Resource, Debug)]
+pub struct Telemetry {
+    /// The samples recorded so far this session.
+    pub samples: Vec<TelemetrySample>,
+
+    /// The CSV file to write samples to.
+    pub path: PathBuf,
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        Self {
+            samples: Vec::new(),
+            path: PathBuf::from("telemetry.csv"),
+        }
+    }
+}
+
+impl Telemetry {
+    /// The CSV header row.
+    pub const HEADER: &str = "time,tank,speed,rotation_rate,shots";
+
+    /// All samples as CSV, starting with [`Self::HEADER`].
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::with_capacity((self.samples.len() + 1) * 32);
+        csv.push_str(Self::HEADER);
+        csv.push('\n');
+        for sample in &self.samples {
+            let _ = writeln!(
+                csv,
+                "{:.4},{},{:.4},{:.4},{}",
+                sample.time, sample.tank, sample.speed, sample.rotation_rate, sample.shots
+            );
+        }
+        csv
+    }
+}
+
+fn record_telemetry(
+    time: Res<Time>,
+    mut telemetry: ResMut<Telemetry>,
+    player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &TankMovementController,
+            Option<&ShotsFired>,
+        ),
+        With<Player>,
+    >,
+    mut previous_angles: Local<EntityHashMap<f32>>,
+) {
+    let dt = time.delta_secs();
+    // Only keep the angles of players that are still around
+    let mut angles = EntityHashMap::default();
+    for (tank, transform, controller, shots) in &player_query {
+        let angle = transform.rotation.to_euler(EulerRot::XYZ).2;
+        angles.insert(tank, angle);
+
+        let Some(&previous_angle) = previous_angles.get(&tank) else {
+            continue;
+        };
+        if dt <= 0.0 {
+            continue;
+        }
+        // Speed comes from the controller, as position deltas jump when the tank wraps around.
+        // Rotation is measured from the transform so commands, replays and input all count the same
+        let turned = shortest_angle_delta(previous_angle, angle);
+        telemetry.samples.push(TelemetrySample {
+            time: time.elapsed_secs(),
+            tank,
+            speed: controller.current_speed,
+            rotation_rate: turned / dt,
+            shots: shots.map_or(0, |shots| shots.0),
+        });
+    }
+    *previous_angles = angles;
+}
+
+fn flush_telemetry(telemetry: Res<Telemetry>) {
+    match std::fs::write(&telemetry.path, telemetry.to_csv()) {
+        Ok(()) => info!(
+            "Wrote {} telemetry samples to {}",
+            telemetry.samples.len(),
+            telemetry.path.display()
+        ),
+        Err(error) => warn!(
+            "Failed to write telemetry to {}: {error}",
+            telemetry.path.display()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    #[test]
+    fn csv_starts_with_the_header() {
+        let telemetry = Telemetry::default();
+        assert_eq!(telemetry.to_csv(), format!("{}\n", Telemetry::HEADER));
+    }
+
+    #[test]
+    fn csv_has_a_row_per_sample() {
+        let tank = Entity::from_raw(7);
+        let telemetry = Telemetry {
+            samples: vec![
+                TelemetrySample {
+                    time: 0.5,
+                    tank,
+                    speed: 120.0,
+                    rotation_rate: -1.25,
+                    shots: 0,
+                },
+                TelemetrySample {
+                    time: 1.0,
+                    tank,
+                    speed: -40.0,
+                    rotation_rate: 0.0,
+                    shots: 3,
+                },
+            ],
+            ..default()
+        };
+        let csv = telemetry.to_csv();
+        let rows: Vec<String> = csv.lines().map(str::to_owned).collect();
+        assert_eq!(
+            rows,
+            vec![
+                Telemetry::HEADER.to_owned(),
+                format!("0.5000,{tank},120.0000,-1.2500,0"),
+                format!("1.0000,{tank},-40.0000,0.0000,3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn each_player_tank_gets_its_own_rows() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        app.init_resource::<Telemetry>();
+        app.add_systems(Update, record_telemetry);
+        let tanks = [(300.0, 2), (-50.0, 5)].map(|(current_speed, shots)| {
+            app.world_mut()
+                .spawn((
+                    Player,
+                    Transform::default(),
+                    TankMovementController {
+                        current_speed,
+                        ..default()
+                    },
+                    ShotsFired(shots),
+                ))
+                .id()
+        });
+
+        // The first update only starts the clock and remembers each tank's angle
+        app.update();
+        app.update();
+
+        let samples = &app.world().resource::<Telemetry>().samples;
+        assert_eq!(samples.len(), 2);
+        for (tank, (speed, shots)) in tanks.into_iter().zip([(300.0, 2), (-50.0, 5)]) {
+            let sample = samples.iter().find(|sample| sample.tank == tank).unwrap();
+            assert_eq!(sample.speed, speed);
+            assert_eq!(sample.shots, shots);
+        }
+    }
+}
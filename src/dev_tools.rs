@@ -1,7 +1,12 @@
 //! Development tools for the game. This plugin is only enabled in dev builds.
 
+use std::path::PathBuf;
+
 use bevy::{
-    dev_tools::states::log_transitions, input::common_conditions::input_just_pressed, prelude::*,
+    dev_tools::states::log_transitions,
+    input::common_conditions::input_just_pressed,
+    prelude::*,
+    render::view::screenshot::{Screenshot, save_to_disk},
     ui::UiDebugOptions,
 };
 
@@ -16,10 +21,147 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         toggle_debug_ui.run_if(input_just_pressed(TOGGLE_KEY)),
     );
+
+    // Dump frames to disk for GIFs and trailers.
+    app.register_type::<FrameDump>();
+    app.init_resource::<FrameDump>();
+    app.add_systems(
+        Update,
+        (
+            toggle_frame_dump.run_if(input_just_pressed(FRAME_DUMP_KEY)),
+            dump_frames.run_if(|frame_dump: Res<FrameDump>| frame_dump.enabled),
+        )
+            .chain(),
+    );
 }
 
 const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
 
+const FRAME_DUMP_KEY: KeyCode = KeyCode::F10;
+
 fn toggle_debug_ui(mut options: ResMut<UiDebugOptions>) {
     options.toggle();
 }
+
+/// Saves the primary window to numbered PNGs at a fixed rate while enabled.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+struct FrameDump {
+    /// Whether frames are currently being dumped.
+    enabled: bool,
+
+    /// How many frames to capture per second of real time.
+    fps: f32,
+
+    /// The directory to write frames to. It's created if missing.
+    dir: PathBuf,
+}
+
+impl Default for FrameDump {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            fps: 30.0,
+            dir: PathBuf::from("frames"),
+        }
+    }
+}
+
+fn toggle_frame_dump(mut frame_dump: ResMut<FrameDump>) {
+    frame_dump.enabled = !frame_dump.enabled;
+    info!(
+        "Frame dump {}",
+        if frame_dump.enabled {
+            "started"
+        } else {
+            "stopped"
+        }
+    );
+}
+
+fn dump_frames(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    frame_dump: Res<FrameDump>,
+    mut clock: Local<CaptureClock>,
+    mut frame_index: Local<u32>,
+) {
+    let interval = 1.0 / frame_dump.fps.max(f32::EPSILON);
+    if frame_dump.is_changed() {
+        clock.restart();
+        if let Err(error) = std::fs::create_dir_all(&frame_dump.dir) {
+            warn!(
+                "Failed to create frame dump directory {}: {error}",
+                frame_dump.dir.display()
+            );
+        }
+    }
+    if !clock.tick(time.delta_secs(), interval) {
+        return;
+    }
+
+    let path = frame_dump
+        .dir
+        .join(format!("frame_{:05}.png", *frame_index));
+    *frame_index += 1;
+    commands
+        .spawn(Screenshot::primary_window())
+        .observe(save_to_disk(path));
+}
+
+/// Decides which frames a [`FrameDump`] captures.
+#[derive(Debug, Default)]
+struct CaptureClock {
+    /// Seconds since the last capture, or `None` to capture on the next tick.
+    since_last_capture: Option<f32>,
+}
+
+impl CaptureClock {
+    /// Start over, capturing on the next tick.
+    fn restart(&mut self) {
+        self.since_last_capture = None;
+    }
+
+    /// Advance by `delta` seconds, returning whether to capture this frame.
+    fn tick(&mut self, delta: f32, interval: f32) -> bool {
+        let Some(since_last_capture) = self.since_last_capture.as_mut() else {
+            self.since_last_capture = Some(0.0);
+            return true;
+        };
+        // Capture at most one frame per update, keeping the remainder so the cadence doesn't drift
+        *since_last_capture += delta;
+        if *since_last_capture < interval {
+            return false;
+        }
+        *since_last_capture = (*since_last_capture - interval).min(interval);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_every_nth_frame() {
+        let mut clock = CaptureClock::default();
+        clock.restart();
+        // Frames of 0.125s at 2 fps capture every 4th frame, starting right away
+        let captured: Vec<bool> = (0..9).map(|_| clock.tick(0.125, 0.5)).collect();
+        assert_eq!(
+            captured,
+            [true, false, false, false, true, false, false, false, true]
+        );
+    }
+
+    #[test]
+    fn restarting_captures_right_away() {
+        let mut clock = CaptureClock::default();
+        clock.restart();
+        assert!(clock.tick(0.125, 0.5));
+        assert!(!clock.tick(0.125, 0.5));
+        clock.restart();
+        assert!(clock.tick(0.125, 0.5));
+        assert!(!clock.tick(0.125, 0.5));
+    }
+}
@@ -0,0 +1,139 @@
+// Support configuring Bevy lints within code.
+#![cfg_attr(bevy_lint, feature(register_tool), register_tool(bevy))]
+//! Tanks War, as a library so other apps can embed the game.
+//!
+//! [`AppPlugin`] sets up the whole game on top of `DefaultPlugins`. To embed only
+//! the gameplay, add [`TanksDemoPlugin`] and configure the [`AppSystems`] and
+//! [`PausableSystems`] sets the same way [`AppPlugin`] does.
+
+mod asset_tracking;
+mod audio;
+pub mod demo;
+#[cfg(feature = "dev")]
+mod dev_tools;
+mod menus;
+mod screens;
+mod theme;
+
+use bevy::{asset::AssetMetaCheck, ecs::system::ScheduleSystem, prelude::*};
+
+pub use demo::TanksDemoPlugin;
+
+/// The whole game: Bevy's default plugins, the menus and screens, and the demo gameplay.
+pub struct AppPlugin;
+
+impl Plugin for AppPlugin {
+    fn build(&self, app: &mut App) {
+        // Add Bevy plugins.
+        app.add_plugins(
+            DefaultPlugins
+                .set(AssetPlugin {
+                    // Wasm builds will check for meta files (that don't exist) if this isn't set.
+                    // This causes errors and even panics on web build on itch.
+                    // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
+                    meta_check: AssetMetaCheck::Never,
+                    ..default()
+                })
+                .set(WindowPlugin {
+                    primary_window: Window {
+                        title: "Tanks War".to_string(),
+                        fit_canvas_to_parent: true,
+                        ..default()
+                    }
+                    .into(),
+                    ..default()
+                }),
+        );
+
+        // Add other plugins.
+        app.add_plugins((
+            asset_tracking::plugin,
+            audio::plugin,
+            demo::TanksDemoPlugin::default(),
+            #[cfg(feature = "dev")]
+            dev_tools::plugin,
+            menus::plugin,
+            screens::plugin,
+            theme::plugin,
+        ));
+
+        configure_system_sets(app);
+
+        // Spawn the main camera.
+        app.add_systems(Startup, spawn_camera);
+    }
+}
+
+/// Order the [`AppSystems`] sets and set up the [`Pause`] state for [`PausableSystems`].
+/// Apps that embed [`TanksDemoPlugin`] without [`AppPlugin`] should call this too.
+pub fn configure_system_sets(app: &mut App) {
+    // Order new `AppSystems` variants by adding them here:
+    app.configure_sets(
+        Update,
+        (
+            AppSystems::TickTimers,
+            AppSystems::RecordInput,
+            AppSystems::Update,
+        )
+            .chain(),
+    );
+
+    // Set up the `Pause` state.
+    app.init_state::<Pause>();
+    app.configure_sets(Update, PausableSystems.run_if(in_state(Pause(false))));
+}
+
+/// High-level groupings of systems for the app in the `Update` schedule.
+/// When adding a new variant, make sure to order it in [`configure_system_sets`].
+#[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum AppSystems {
+    /// Tick timers.
+    /// Gameplay timers (cooldowns, reloads, lifetimes, ...) should be added with
+    /// [`AddGameplayTimers::add_gameplay_timers`] so they don't advance while the game is paused.
+    TickTimers,
+    /// Record player input.
+    RecordInput,
+    /// Do everything else (consider splitting this into further variants).
+    Update,
+}
+
+/// Whether or not the game is paused.
+#[derive(States, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[states(scoped_entities)]
+pub struct Pause(pub bool);
+
+/// A system set for systems that shouldn't run while the game is paused.
+///
+/// This includes ticking gameplay timers: a timer that is ticked outside of
+/// this set keeps running during pause, e.g. a cooldown would finish while the
+/// pause menu is open.
+#[derive(SystemSet, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct PausableSystems;
+
+/// Registers systems that tick gameplay timers, so that pausing stops them.
+pub trait AddGameplayTimers {
+    /// Add systems that tick gameplay timers (cooldowns, reloads, lifetimes, ...)
+    /// in [`AppSystems::TickTimers`] and [`PausableSystems`].
+    fn add_gameplay_timers<M>(
+        &mut self,
+        systems: impl IntoScheduleConfigs<ScheduleSystem, M>,
+    ) -> &mut Self;
+}
+
+impl AddGameplayTimers for App {
+    fn add_gameplay_timers<M>(
+        &mut self,
+        systems: impl IntoScheduleConfigs<ScheduleSystem, M>,
+    ) -> &mut Self {
+        self.add_systems(
+            Update,
+            systems
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+        )
+    }
+}
+
+fn spawn_camera(mut commands: Commands) {
+    commands.spawn((Name::new("Camera"), Camera2d));
+}
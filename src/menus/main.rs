@@ -2,7 +2,9 @@
 
 use bevy::prelude::*;
 
-use crate::{asset_tracking::ResourceHandles, menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    asset_tracking::ResourceHandles, demo::DemoConfig, menus::Menu, screens::Screen, theme::widget,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Main), spawn_main_menu);
@@ -15,22 +17,43 @@ fn spawn_main_menu(mut commands: Commands) {
         StateScoped(Menu::Main),
         #[cfg(not(target_family = "wasm"))]
         children![
-            widget::button("Play", enter_loading_or_gameplay_screen),
+            widget::button("Play", play_single_player),
+            widget::button("2 Players", play_two_players),
             widget::button("Settings", open_settings_menu),
             widget::button("Credits", open_credits_menu),
             widget::button("Exit", exit_app),
         ],
         #[cfg(target_family = "wasm")]
         children![
-            widget::button("Play", enter_loading_or_gameplay_screen),
+            widget::button("Play", play_single_player),
+            widget::button("2 Players", play_two_players),
             widget::button("Settings", open_settings_menu),
             widget::button("Credits", open_credits_menu),
         ],
     ));
 }
 
-fn enter_loading_or_gameplay_screen(
+fn play_single_player(
+    _: Trigger<Pointer<Click>>,
+    mut config: ResMut<DemoConfig>,
+    resource_handles: Res<ResourceHandles>,
+    next_screen: ResMut<NextState<Screen>>,
+) {
+    config.two_players = false;
+    enter_loading_or_gameplay_screen(resource_handles, next_screen);
+}
+
+fn play_two_players(
     _: Trigger<Pointer<Click>>,
+    mut config: ResMut<DemoConfig>,
+    resource_handles: Res<ResourceHandles>,
+    next_screen: ResMut<NextState<Screen>>,
+) {
+    config.two_players = true;
+    enter_loading_or_gameplay_screen(resource_handles, next_screen);
+}
+
+fn enter_loading_or_gameplay_screen(
     resource_handles: Res<ResourceHandles>,
     mut next_screen: ResMut<NextState<Screen>>,
 ) {
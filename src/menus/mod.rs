@@ -2,6 +2,7 @@
 
 mod credits;
 mod main;
+mod navigation;
 mod pause;
 mod settings;
 
@@ -13,6 +14,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         credits::plugin,
         main::plugin,
+        navigation::plugin,
         settings::plugin,
         pause::plugin,
     ));
@@ -0,0 +1,373 @@
+//! Keyboard navigation for menus, with key repeat.
+//!
+//! Holding a direction key emits a [`MenuNavigate`] event right away, then
+//! again after [`RepeatInput::initial_delay`], and then at
+//! [`RepeatInput::repeat_rate`] for as long as it's held.
+//! Navigate events move the [`MenuFocus`] between buttons, and Enter or Space
+//! clicks the focused one.
+
+use std::time::Duration;
+
+use bevy::{
+    input::common_conditions::input_just_pressed,
+    picking::{
+        backend::HitData,
+        pointer::{Location, PointerButton, PointerId},
+    },
+    prelude::*,
+    render::camera::NormalizedRenderTarget,
+    window::{PrimaryWindow, WindowRef},
+};
+
+use crate::{menus::Menu, theme::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<RepeatInput>();
+    app.init_resource::<RepeatInput>();
+    app.register_type::<MenuNavigate>();
+    app.add_event::<MenuNavigate>();
+    app.register_type::<MenuFocus>();
+    app.init_resource::<MenuFocus>();
+    app.init_resource::<HeldNavigateKey>();
+    app.register_type::<MenuInputCaptured>();
+    app.init_resource::<MenuInputCaptured>();
+
+    // Forget the held key and focus when a menu closes, so reopening it starts fresh.
+    for menu in [Menu::Main, Menu::Credits, Menu::Settings, Menu::Pause] {
+        app.add_systems(OnExit(menu), reset_menu_navigation);
+    }
+    app.add_systems(
+        Update,
+        (
+            emit_navigate_events,
+            move_menu_focus,
+            highlight_menu_focus.run_if(resource_changed::<MenuFocus>),
+            click_menu_focus
+                .run_if(input_just_pressed(KeyCode::Enter).or(input_just_pressed(KeyCode::Space))),
+        )
+            .chain()
+            .in_set(MenuNavigationSystems)
+            .run_if(not(in_state(Menu::None)).and(not(menu_input_captured))),
+    );
+}
+
+/// The systems that turn keys into menu navigation.
+/// Systems that capture raw keys with [`MenuInputCaptured`] should run after these.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MenuNavigationSystems;
+
+/// Set while a menu widget takes raw keyboard input (e.g. to rebind a key),
+/// so the keys it reads don't also navigate or click.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct MenuInputCaptured(pub bool);
+
+fn menu_input_captured(captured: Res<MenuInputCaptured>) -> bool {
+    captured.0
+}
+
+/// How held direction keys repeat while navigating menus.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct RepeatInput {
+    /// Seconds a key has to be held before it starts repeating.
+    pub initial_delay: f32,
+
+    /// Repeats per second once a key is repeating.
+    pub repeat_rate: f32,
+}
+
+impl Default for RepeatInput {
+    fn default() -> Self {
+        Self {
+            initial_delay: 0.4,
+            repeat_rate: 10.0,
+        }
+    }
+}
+
+/// A discrete step through a menu's options.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum MenuNavigate {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// The button focused by keyboard navigation, if any.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct MenuFocus(pub Option<Entity>);
+
+const NAVIGATE_KEYS: [(KeyCode, MenuNavigate); 4] = [
+    (KeyCode::ArrowUp, MenuNavigate::Up),
+    (KeyCode::ArrowDown, MenuNavigate::Down),
+    (KeyCode::ArrowLeft, MenuNavigate::Left),
+    (KeyCode::ArrowRight, MenuNavigate::Right),
+];
+
+/// The direction key being repeated, and the time until its next repeat.
+struct HeldKey {
+    key: KeyCode,
+    direction: MenuNavigate,
+    until_repeat: f32,
+}
+
+#[derive(Resource, Default)]
+struct HeldNavigateKey(Option<HeldKey>);
+
+fn reset_menu_navigation(mut held: ResMut<HeldNavigateKey>, mut focus: ResMut<MenuFocus>) {
+    held.0 = None;
+    focus.0 = None;
+}
+
+fn emit_navigate_events(
+    time: Res<Time>,
+    input: Res<ButtonInput<KeyCode>>,
+    repeat: Res<RepeatInput>,
+    mut held: ResMut<HeldNavigateKey>,
+    mut navigate_events: EventWriter<MenuNavigate>,
+) {
+    // A newly pressed key takes over from the one being held
+    if let Some(&(key, direction)) = NAVIGATE_KEYS
+        .iter()
+        .find(|(key, _)| input.just_pressed(*key))
+    {
+        navigate_events.write(direction);
+        held.0 = Some(HeldKey {
+            key,
+            direction,
+            until_repeat: repeat.initial_delay,
+        });
+        return;
+    }
+
+    let Some(held_key) = held.0.as_mut() else {
+        return;
+    };
+    if !input.pressed(held_key.key) {
+        held.0 = None;
+        return;
+    }
+
+    held_key.until_repeat -= time.delta_secs();
+    if repeat.repeat_rate <= 0.0 {
+        return;
+    }
+    while held_key.until_repeat <= 0.0 {
+        navigate_events.write(held_key.direction);
+        held_key.until_repeat += repeat.repeat_rate.recip();
+    }
+}
+
+/// Step the focus through the menu's buttons in reading order.
+/// Up and Left go back, Down and Right go forward, wrapping around at the ends.
+fn move_menu_focus(
+    mut navigate_events: EventReader<MenuNavigate>,
+    mut focus: ResMut<MenuFocus>,
+    button_query: Query<(Entity, &GlobalTransform), With<Button>>,
+) {
+    let mut buttons: Vec<_> = button_query
+        .iter()
+        .map(|(entity, transform)| (entity, transform.translation().xy()))
+        .collect();
+    if buttons.is_empty() {
+        navigate_events.clear();
+        return;
+    }
+    // UI y grows downward, so sort top to bottom, then left to right
+    buttons.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    for direction in navigate_events.read() {
+        let current = focus
+            .0
+            .and_then(|focused| buttons.iter().position(|&(entity, _)| entity == focused));
+        let len = buttons.len();
+        let next = match (current, direction) {
+            (None, MenuNavigate::Up | MenuNavigate::Left) => len - 1,
+            (None, MenuNavigate::Down | MenuNavigate::Right) => 0,
+            (Some(index), MenuNavigate::Up | MenuNavigate::Left) => (index + len - 1) % len,
+            (Some(index), MenuNavigate::Down | MenuNavigate::Right) => (index + 1) % len,
+        };
+        focus.0 = Some(buttons[next].0);
+    }
+}
+
+/// Show the focused button as hovered.
+fn highlight_menu_focus(
+    focus: Res<MenuFocus>,
+    mut button_query: Query<
+        (
+            Entity,
+            &Interaction,
+            &InteractionPalette,
+            &mut BackgroundColor,
+        ),
+        With<Button>,
+    >,
+) {
+    for (entity, interaction, palette, mut background) in &mut button_query {
+        *background = match interaction {
+            Interaction::Pressed => palette.pressed,
+            _ if focus.0 == Some(entity) => palette.hovered,
+            Interaction::Hovered => palette.hovered,
+            Interaction::None => palette.none,
+        }
+        .into();
+    }
+}
+
+/// Click the focused button, running the same action as clicking it with the mouse.
+fn click_menu_focus(
+    mut commands: Commands,
+    focus: Res<MenuFocus>,
+    button_query: Query<(), With<Button>>,
+    window: Query<Entity, With<PrimaryWindow>>,
+    camera: Query<Entity, With<Camera2d>>,
+) {
+    let Some(button) = focus.0.filter(|&button| button_query.contains(button)) else {
+        return;
+    };
+    let (Ok(window), Some(camera)) = (window.single(), camera.iter().next()) else {
+        return;
+    };
+    let Some(target) = WindowRef::Primary.normalize(Some(window)) else {
+        return;
+    };
+    let location = Location {
+        target: NormalizedRenderTarget::Window(target),
+        position: Vec2::ZERO,
+    };
+    let click = Click {
+        button: PointerButton::Primary,
+        hit: HitData::new(camera, 0.0, None, None),
+        duration: Duration::ZERO,
+    };
+    commands.trigger_targets(
+        Pointer::new(PointerId::Mouse, location, button, click),
+        button,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{state::app::StatesPlugin, time::TimeUpdateStrategy};
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct NavigateCount(usize);
+
+    fn count_navigate_events(
+        mut navigate_events: EventReader<MenuNavigate>,
+        mut count: ResMut<NavigateCount>,
+    ) {
+        count.0 += navigate_events.read().count();
+    }
+
+    fn navigation_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_state::<Menu>();
+        app.insert_resource(RepeatInput {
+            initial_delay: 0.25,
+            repeat_rate: 5.0,
+        });
+        app.init_resource::<NavigateCount>();
+        app.add_event::<MenuNavigate>();
+        app.init_resource::<HeldNavigateKey>();
+        app.init_resource::<MenuFocus>();
+        app.add_systems(OnExit(Menu::Pause), reset_menu_navigation);
+        app.add_systems(
+            Update,
+            (emit_navigate_events, count_navigate_events)
+                .chain()
+                .run_if(not(in_state(Menu::None))),
+        );
+        app.world_mut()
+            .resource_mut::<NextState<Menu>>()
+            .set(Menu::Pause);
+        // The first update only starts the clock and enters the menu
+        app.update();
+        app
+    }
+
+    /// Hold `key` for `frames` updates after the one it was pressed in.
+    fn hold(app: &mut App, key: KeyCode, frames: usize) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+        app.update();
+        // Only the first frame counts as just pressed
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+        for _ in 0..frames {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn holding_a_key_repeats_at_the_configured_rate() {
+        let mut app = navigation_app();
+        // One event on press, then repeats at 0.25s, 0.45s, 0.65s and 0.85s
+        hold(&mut app, KeyCode::ArrowDown, 9);
+        assert_eq!(app.world().resource::<NavigateCount>().0, 5);
+    }
+
+    #[test]
+    fn releasing_a_key_stops_the_repeat() {
+        let mut app = navigation_app();
+        hold(&mut app, KeyCode::ArrowUp, 1);
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(KeyCode::ArrowUp);
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(app.world().resource::<NavigateCount>().0, 1);
+    }
+
+    #[test]
+    fn closing_the_menu_forgets_the_held_key() {
+        let mut app = navigation_app();
+        hold(&mut app, KeyCode::ArrowDown, 1);
+        app.world_mut()
+            .resource_mut::<NextState<Menu>>()
+            .set(Menu::None);
+        app.update();
+        assert!(app.world().resource::<HeldNavigateKey>().0.is_none());
+    }
+
+    #[test]
+    fn focus_steps_through_buttons_in_reading_order() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_event::<MenuNavigate>();
+        app.init_resource::<MenuFocus>();
+        app.add_systems(Update, move_menu_focus);
+        let world = app.world_mut();
+        let bottom = world
+            .spawn((Button, GlobalTransform::from_xyz(0.0, 200.0, 0.0)))
+            .id();
+        let top = world
+            .spawn((Button, GlobalTransform::from_xyz(0.0, 100.0, 0.0)))
+            .id();
+
+        let navigate = |app: &mut App, direction| {
+            app.world_mut().send_event(direction);
+            app.update();
+            app.world().resource::<MenuFocus>().0
+        };
+        assert_eq!(navigate(&mut app, MenuNavigate::Down), Some(top));
+        assert_eq!(navigate(&mut app, MenuNavigate::Down), Some(bottom));
+        // Wraps around
+        assert_eq!(navigate(&mut app, MenuNavigate::Right), Some(top));
+        assert_eq!(navigate(&mut app, MenuNavigate::Up), Some(bottom));
+    }
+}
@@ -2,15 +2,39 @@
 //!
 //! Additional settings and accessibility options should go here.
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+use bevy::{
+    audio::Volume,
+    ecs::{spawn::SpawnWith, system::IntoObserverSystem},
+    input::common_conditions::input_just_pressed,
+    prelude::*,
+    ui::Val::*,
+};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{
+    demo::{
+        DemoConfig,
+        input_command::{InputAction, KeyBindingSet, KeyBindings, RESERVED_KEYS, key_label},
+        tank_movement::InvertControls,
+    },
+    menus::{
+        Menu,
+        navigation::{MenuInputCaptured, MenuNavigationSystems},
+    },
+    screens::Screen,
+    theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
     app.add_systems(
         Update,
-        go_back.run_if(in_state(Menu::Settings).and(input_just_pressed(KeyCode::Escape))),
+        go_back
+            .run_if(
+                in_state(Menu::Settings)
+                    .and(input_just_pressed(KeyCode::Escape))
+                    .and(not(is_rebinding)),
+            )
+            .before(capture_key_binding),
     );
 
     app.register_type::<GlobalVolumeLabel>();
@@ -18,6 +42,33 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         update_global_volume_label.run_if(in_state(Menu::Settings)),
     );
+
+    app.register_type::<InvertControlsLabel>();
+    app.add_systems(
+        Update,
+        update_invert_controls_labels.run_if(in_state(Menu::Settings)),
+    );
+
+    app.register_type::<KeyRebinding>();
+    app.init_resource::<KeyRebinding>();
+    app.register_type::<ShownKeyBindings>();
+    app.init_resource::<ShownKeyBindings>();
+    app.register_type::<KeyBindingLabel>();
+    app.register_type::<KeyBindingSetLabel>();
+    app.add_systems(OnEnter(Menu::Settings), show_key_bindings_in_use);
+    app.add_systems(OnExit(Menu::Settings), cancel_key_rebinding);
+    app.add_systems(
+        Update,
+        (
+            capture_key_binding,
+            capture_menu_input.run_if(resource_changed::<KeyRebinding>),
+            update_key_binding_labels,
+            update_key_binding_set_label,
+        )
+            .chain()
+            .after(MenuNavigationSystems)
+            .run_if(in_state(Menu::Settings)),
+    );
 }
 
 fn spawn_settings_menu(mut commands: Commands) {
@@ -28,6 +79,7 @@ fn spawn_settings_menu(mut commands: Commands) {
         children![
             widget::header("Settings"),
             settings_grid(),
+            controls_grid(),
             widget::button("Back", go_back_on_click),
         ],
     ));
@@ -52,6 +104,42 @@ fn settings_grid() -> impl Bundle {
                 }
             ),
             global_volume_widget(),
+            (
+                widget::label("Invert Steering"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            invert_controls_widget(
+                "Invert Steering Widget",
+                InvertControlsLabel::Steering,
+                toggle_invert_steering,
+            ),
+            (
+                widget::label("Invert Throttle"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            invert_controls_widget(
+                "Invert Throttle Widget",
+                InvertControlsLabel::Throttle,
+                toggle_invert_throttle,
+            ),
+            (
+                widget::label("Invert Turret"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            invert_controls_widget(
+                "Invert Turret Widget",
+                InvertControlsLabel::Turret,
+                toggle_invert_turret,
+            ),
         ],
     )
 }
@@ -104,6 +192,259 @@ fn update_global_volume_label(
     label.0 = format!("{percent:3.0}%");
 }
 
+fn invert_controls_widget<E, B, M, I>(
+    name: &'static str,
+    label: InvertControlsLabel,
+    toggle: I,
+) -> impl Bundle
+where
+    E: Event,
+    B: Bundle,
+    I: IntoObserverSystem<E, B, M>,
+{
+    (
+        Name::new(name),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<>", toggle),
+            (
+                Name::new("Current Value"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), label)],
+            ),
+        ],
+    )
+}
+
+fn toggle_invert_steering(_: Trigger<Pointer<Click>>, mut invert: ResMut<InvertControls>) {
+    invert.steering = !invert.steering;
+}
+
+fn toggle_invert_throttle(_: Trigger<Pointer<Click>>, mut invert: ResMut<InvertControls>) {
+    invert.throttle = !invert.throttle;
+}
+
+fn toggle_invert_turret(_: Trigger<Pointer<Click>>, mut invert: ResMut<InvertControls>) {
+    invert.turret = !invert.turret;
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum InvertControlsLabel {
+    Steering,
+    Throttle,
+    Turret,
+}
+
+fn update_invert_controls_labels(
+    invert: Res<InvertControls>,
+    mut label_query: Query<(&InvertControlsLabel, &mut Text)>,
+) {
+    for (label, mut text) in &mut label_query {
+        let inverted = match label {
+            InvertControlsLabel::Steering => invert.steering,
+            InvertControlsLabel::Throttle => invert.throttle,
+            InvertControlsLabel::Turret => invert.turret,
+        };
+        text.0 = if inverted { "On" } else { "Off" }.to_string();
+    }
+}
+
+fn controls_grid() -> impl Bundle {
+    (
+        Name::new("Controls Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            ..default()
+        },
+        Children::spawn(SpawnWith(|parent: &mut ChildSpawner| {
+            parent.spawn((
+                widget::label("Key Bindings"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                },
+            ));
+            parent.spawn(key_binding_set_widget());
+            for action in InputAction::ALL {
+                parent.spawn((
+                    widget::label(action.label()),
+                    Node {
+                        justify_self: JustifySelf::End,
+                        ..default()
+                    },
+                ));
+                parent.spawn(key_binding_widget(action));
+            }
+        })),
+    )
+}
+
+fn key_binding_set_widget() -> impl Bundle {
+    (
+        Name::new("Key Binding Set Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<>", show_next_key_bindings),
+            (
+                Name::new("Current Binding Set"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), KeyBindingSetLabel)],
+            ),
+        ],
+    )
+}
+
+/// Whose key bindings the controls grid shows and rebinds.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+struct ShownKeyBindings(KeyBindingSet);
+
+/// Open the settings on the key bindings of the players in the current game mode.
+fn show_key_bindings_in_use(config: Option<Res<DemoConfig>>, mut shown: ResMut<ShownKeyBindings>) {
+    shown.0 = if config.is_some_and(|config| config.two_players) {
+        KeyBindingSet::PlayerOne
+    } else {
+        KeyBindingSet::SinglePlayer
+    };
+}
+
+fn show_next_key_bindings(
+    _: Trigger<Pointer<Click>>,
+    mut shown: ResMut<ShownKeyBindings>,
+    mut rebinding: ResMut<KeyRebinding>,
+) {
+    let index = KeyBindingSet::ALL
+        .iter()
+        .position(|&set| set == shown.0)
+        .unwrap_or_default();
+    shown.0 = KeyBindingSet::ALL[(index + 1) % KeyBindingSet::ALL.len()];
+    rebinding.0 = None;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct KeyBindingSetLabel;
+
+fn update_key_binding_set_label(
+    shown: Res<ShownKeyBindings>,
+    mut label: Single<&mut Text, With<KeyBindingSetLabel>>,
+) {
+    label.0 = shown.0.label().to_string();
+}
+
+fn key_binding_widget(action: InputAction) -> impl Bundle {
+    (
+        Name::new(format!("{} Binding Widget", action.label())),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small(
+                "<>",
+                move |_: Trigger<Pointer<Click>>, mut rebinding: ResMut<KeyRebinding>| {
+                    rebinding.0 = Some(action);
+                },
+            ),
+            (
+                Name::new("Current Key"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), KeyBindingLabel(action))],
+            ),
+        ],
+    )
+}
+
+/// The action waiting for its next key press, if any.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+struct KeyRebinding(Option<InputAction>);
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+struct KeyBindingLabel(InputAction);
+
+fn is_rebinding(rebinding: Res<KeyRebinding>) -> bool {
+    rebinding.0.is_some()
+}
+
+/// Bind the action being rebound to the next key pressed, swapping with any action
+/// already using it. Escape cancels, and keys reserved for dev tools are ignored.
+fn capture_key_binding(
+    input: Res<ButtonInput<KeyCode>>,
+    mut rebinding: ResMut<KeyRebinding>,
+    shown: Res<ShownKeyBindings>,
+    mut bindings: ResMut<KeyBindings>,
+) {
+    let Some(action) = rebinding.0 else {
+        return;
+    };
+    // Skip the frame the rebind started, so the Enter that clicked the button isn't bound
+    if rebinding.is_changed() {
+        return;
+    }
+    let Some(&key) = input
+        .get_just_pressed()
+        .find(|key| !RESERVED_KEYS.contains(key))
+    else {
+        return;
+    };
+    if key != KeyCode::Escape {
+        bindings.set(shown.0, action, key);
+    }
+    rebinding.0 = None;
+}
+
+fn cancel_key_rebinding(
+    mut rebinding: ResMut<KeyRebinding>,
+    mut captured: ResMut<MenuInputCaptured>,
+) {
+    rebinding.0 = None;
+    captured.0 = false;
+}
+
+/// Keep menu navigation off the keys while one is being rebound.
+fn capture_menu_input(rebinding: Res<KeyRebinding>, mut captured: ResMut<MenuInputCaptured>) {
+    captured.0 = rebinding.0.is_some();
+}
+
+fn update_key_binding_labels(
+    bindings: Res<KeyBindings>,
+    shown: Res<ShownKeyBindings>,
+    rebinding: Res<KeyRebinding>,
+    mut label_query: Query<(&KeyBindingLabel, &mut Text)>,
+) {
+    for (KeyBindingLabel(action), mut text) in &mut label_query {
+        text.0 = if rebinding.0 == Some(*action) {
+            "Press a key...".to_string()
+        } else {
+            key_label(bindings.keys(shown.0).get(*action))
+        };
+    }
+}
+
 fn go_back_on_click(
     _: Trigger<Pointer<Click>>,
     screen: Res<State<Screen>>,
@@ -123,3 +464,66 @@ fn go_back(screen: Res<State<Screen>>, mut next_menu: ResMut<NextState<Menu>>) {
         Menu::Pause
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebinding_ignores_reserved_keys() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<KeyBindings>();
+        app.init_resource::<ShownKeyBindings>();
+        app.insert_resource(KeyRebinding(Some(InputAction::Fire)));
+        app.add_systems(Update, capture_key_binding);
+        // The frame the rebind starts is skipped
+        app.update();
+
+        let press = |app: &mut App, key| {
+            let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            input.clear();
+            input.press(key);
+            app.update();
+        };
+        press(&mut app, KeyCode::F9);
+        assert_eq!(
+            app.world().resource::<KeyRebinding>().0,
+            Some(InputAction::Fire)
+        );
+        assert_eq!(
+            app.world().resource::<KeyBindings>().single_player.fire,
+            KeyCode::Space
+        );
+
+        press(&mut app, KeyCode::KeyF);
+        assert_eq!(app.world().resource::<KeyRebinding>().0, None);
+        assert_eq!(
+            app.world().resource::<KeyBindings>().single_player.fire,
+            KeyCode::KeyF
+        );
+    }
+
+    #[test]
+    fn rebinding_changes_the_shown_players_keys() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<KeyBindings>();
+        app.insert_resource(ShownKeyBindings(KeyBindingSet::PlayerTwo));
+        app.insert_resource(KeyRebinding(Some(InputAction::Forward)));
+        app.add_systems(Update, capture_key_binding);
+        app.update();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyI);
+        app.update();
+
+        let bindings = app.world().resource::<KeyBindings>();
+        assert_eq!(bindings.player_two.forward, KeyCode::KeyI);
+        assert_eq!(bindings.single_player, KeyBindings::default().single_player);
+        assert_eq!(bindings.player_one, KeyBindings::default().player_one);
+    }
+}
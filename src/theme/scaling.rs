@@ -0,0 +1,106 @@
+//! Scale the UI with the window so text stays readable at any resolution.
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<UiScaling>();
+    app.init_resource::<UiScaling>();
+
+    app.add_systems(Update, update_ui_scale);
+}
+
+/// How [`UiScale`] follows the window size.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct UiScaling {
+    /// The window size the UI was laid out for, at which the scale is 1.0.
+    pub reference_resolution: Vec2,
+
+    /// The smallest scale the UI is allowed to shrink to.
+    pub min_scale: f32,
+
+    /// The largest scale the UI is allowed to grow to.
+    pub max_scale: f32,
+}
+
+impl Default for UiScaling {
+    fn default() -> Self {
+        Self {
+            reference_resolution: Vec2::new(1280.0, 720.0),
+            min_scale: 0.5,
+            max_scale: 3.0,
+        }
+    }
+}
+
+impl UiScaling {
+    /// The UI scale for a window of the given size.
+    /// If `min_scale` is above `max_scale`, `max_scale` wins.
+    pub fn scale_for(&self, window_size: Vec2) -> f32 {
+        // Fit the reference layout inside the window on both axes
+        let ratio = window_size / self.reference_resolution;
+        // Not `clamp`, which panics if the bounds are edited out of order
+        ratio.min_element().max(self.min_scale).min(self.max_scale)
+    }
+}
+
+fn update_ui_scale(
+    scaling: Res<UiScaling>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let scale = scaling.scale_for(window.size());
+    if scale.is_finite() && ui_scale.0 != scale {
+        ui_scale.0 = scale;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::window::WindowResolution;
+
+    use super::*;
+
+    #[test]
+    fn scale_fits_the_reference_layout_in_the_window() {
+        let scaling = UiScaling::default();
+        assert_eq!(scaling.scale_for(Vec2::new(1280.0, 720.0)), 1.0);
+        // The narrower axis decides
+        assert_eq!(scaling.scale_for(Vec2::new(2560.0, 1080.0)), 1.5);
+    }
+
+    #[test]
+    fn window_twice_the_reference_doubles_the_ui_scale() {
+        let mut app = App::new();
+        app.init_resource::<UiScaling>();
+        app.init_resource::<UiScale>();
+        app.add_systems(Update, update_ui_scale);
+        app.world_mut().spawn((
+            Window {
+                resolution: WindowResolution::new(2560.0, 1440.0),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        app.update();
+
+        assert_eq!(app.world().resource::<UiScale>().0, 2.0);
+    }
+
+    #[test]
+    fn scale_is_limited_to_its_bounds() {
+        let scaling = UiScaling::default();
+        assert_eq!(scaling.scale_for(Vec2::new(320.0, 180.0)), 0.5);
+        assert_eq!(scaling.scale_for(Vec2::new(7680.0, 4320.0)), 3.0);
+    }
+
+    #[test]
+    fn inverted_bounds_do_not_panic() {
+        let scaling = UiScaling {
+            min_scale: 2.0,
+            max_scale: 1.0,
+            ..default()
+        };
+        assert_eq!(scaling.scale_for(Vec2::new(1280.0, 720.0)), 1.0);
+    }
+}